@@ -18,9 +18,33 @@ extern "C" fn free_slint_component(_state: *mut libc::c_void, ptr: *mut libc::c_
 }
 
 extern "C" fn free_slint_instance(_state: *mut libc::c_void, ptr: *mut libc::c_void) {
+    if !ptr.is_null() {
+        slint::hot_reload_forget(ptr as *mut slint::SlintInstanceWrapper);
+        let mut handlers = slint::watch_forget(ptr as *const slint::SlintInstanceWrapper);
+        let wrapper = unsafe { Box::from_raw(ptr as *mut slint::SlintInstanceWrapper) };
+        handlers.extend(wrapper.callback_handlers());
+        let vm = wrapper.vm_ptr as RingVM;
+        drop(wrapper);
+        // Ring frees the pointer while it is running other code, so the bound
+        // objects are released once the event loop gets back control.
+        i_slint_core::timers::Timer::single_shot(std::time::Duration::ZERO, move || {
+            slint::RingHandler::release_all(vm, handlers)
+        });
+    }
+}
+
+extern "C" fn free_slint_value(_state: *mut libc::c_void, ptr: *mut libc::c_void) {
+    if !ptr.is_null() {
+        unsafe {
+            let _ = Box::from_raw(ptr as *mut slint::SlintValueWrapper);
+        }
+    }
+}
+
+extern "C" fn free_slint_compilation(_state: *mut libc::c_void, ptr: *mut libc::c_void) {
     if !ptr.is_null() {
         unsafe {
-            let _ = Box::from_raw(ptr as *mut slint::SlintInstanceWrapper);
+            let _ = Box::from_raw(ptr as *mut slint::SlintCompilationResult);
         }
     }
 }
@@ -64,6 +88,50 @@ ring_func!(ring_slint_clear_library_paths, |p| {
     slint::clear_library_paths();
 });
 
+ring_func!(ring_slint_add_include_path, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_string!(p, 1);
+
+    let path = ring_get_string!(p, 1);
+    slint::add_include_path(path);
+});
+
+ring_func!(ring_slint_remove_include_path, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_string!(p, 1);
+
+    let path = ring_get_string!(p, 1);
+    slint::remove_include_path(path);
+});
+
+ring_func!(ring_slint_clear_include_paths, |p| {
+    ring_check_paracount!(p, 0);
+    slint::clear_include_paths();
+});
+
+ring_func!(ring_slint_add_virtual_file, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_string!(p, 1);
+    ring_check_string!(p, 2);
+
+    let path = ring_get_string!(p, 1);
+    let source = ring_get_string!(p, 2);
+    slint::add_virtual_file(path, source);
+});
+
+ring_func!(ring_slint_remove_virtual_file, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_string!(p, 1);
+
+    let path = ring_get_string!(p, 1);
+    slint::remove_virtual_file(path);
+});
+
+ring_func!(ring_slint_clear_virtual_files, |p| {
+    ring_check_paracount!(p, 0);
+    slint::clear_virtual_files();
+});
+
 ring_func!(ring_slint_load, |p| {
     ring_check_paracount!(p, 1);
     ring_check_string!(p, 1);
@@ -71,9 +139,8 @@ ring_func!(ring_slint_load, |p| {
     let path = ring_get_string!(p, 1);
 
     match slint::compile_from_path(path) {
-        Ok(definition) => {
-            let wrapper = Box::new(slint::SlintComponentDef { definition });
-            let ptr = Box::into_raw(wrapper);
+        Ok(component) => {
+            let ptr = Box::into_raw(Box::new(component));
             ring_ret_managed_cpointer!(p, ptr, slint::SLINT_COMPONENT_TYPE, free_slint_component);
         }
         Err(e) => {
@@ -91,9 +158,48 @@ ring_func!(ring_slint_loadstring, |p| {
     let path = ring_get_string!(p, 2);
 
     match slint::compile_from_source(source, path) {
-        Ok(definition) => {
-            let wrapper = Box::new(slint::SlintComponentDef { definition });
-            let ptr = Box::into_raw(wrapper);
+        Ok(component) => {
+            let ptr = Box::into_raw(Box::new(component));
+            ring_ret_managed_cpointer!(p, ptr, slint::SLINT_COMPONENT_TYPE, free_slint_component);
+        }
+        Err(e) => {
+            ring_error!(p, &e);
+        }
+    }
+});
+
+ring_func!(ring_slint_load_component, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_string!(p, 1);
+    ring_check_string!(p, 2);
+
+    let path = ring_get_string!(p, 1);
+    let name = ring_get_string!(p, 2);
+
+    match slint::get_component_by_name(path, name) {
+        Ok(component) => {
+            let ptr = Box::into_raw(Box::new(component));
+            ring_ret_managed_cpointer!(p, ptr, slint::SLINT_COMPONENT_TYPE, free_slint_component);
+        }
+        Err(e) => {
+            ring_error!(p, &e);
+        }
+    }
+});
+
+ring_func!(ring_slint_loadstring_component, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_string!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    let source = ring_get_string!(p, 1);
+    let path = ring_get_string!(p, 2);
+    let name = ring_get_string!(p, 3);
+
+    match slint::get_component_by_name_from_source(source, path, name) {
+        Ok(component) => {
+            let ptr = Box::into_raw(Box::new(component));
             ring_ret_managed_cpointer!(p, ptr, slint::SLINT_COMPONENT_TYPE, free_slint_component);
         }
         Err(e) => {
@@ -102,6 +208,243 @@ ring_func!(ring_slint_loadstring, |p| {
     }
 });
 
+ring_func!(ring_slint_component_names, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(comp) =
+        ring_get_pointer!(p, 1, slint::SlintComponentDef, slint::SLINT_COMPONENT_TYPE)
+    {
+        let list = ring_api_newlist(p);
+        for name in slint::component_names(comp) {
+            ring_list_addstring_str(list, &name);
+        }
+        ring_ret_list!(p, list);
+    } else {
+        ring_error!(p, "Invalid SlintComponent pointer");
+    }
+});
+
+ring_func!(ring_slint_component_get, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    if let Some(comp) =
+        ring_get_pointer!(p, 1, slint::SlintComponentDef, slint::SLINT_COMPONENT_TYPE)
+    {
+        let name = ring_get_string!(p, 2);
+        match slint::sibling_component(comp, name) {
+            Ok(component) => {
+                let ptr = Box::into_raw(Box::new(component));
+                ring_ret_managed_cpointer!(
+                    p,
+                    ptr,
+                    slint::SLINT_COMPONENT_TYPE,
+                    free_slint_component
+                );
+            }
+            Err(e) => {
+                ring_error!(p, &e);
+            }
+        }
+    } else {
+        ring_error!(p, "Invalid SlintComponent pointer");
+    }
+});
+
+ring_func!(ring_slint_load_diagnostics, |p| {
+    ring_check_paracount!(p, 0);
+
+    let list = ring_api_newlist(p);
+    for diag in slint::last_diagnostics() {
+        let record = ring_list_newlist(list);
+        add_diagnostic_fields(record, &diag);
+    }
+    ring_ret_list!(p, list);
+});
+
+ring_func!(ring_slint_compile, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_string!(p, 1);
+
+    let path = ring_get_string!(p, 1);
+    let ptr = Box::into_raw(Box::new(slint::compile_result_from_path(path)));
+    ring_ret_managed_cpointer!(
+        p,
+        ptr,
+        slint::SLINT_COMPILATION_TYPE,
+        free_slint_compilation
+    );
+});
+
+ring_func!(ring_slint_compile_string, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_string!(p, 1);
+    ring_check_string!(p, 2);
+
+    let source = ring_get_string!(p, 1);
+    let path = ring_get_string!(p, 2);
+    let ptr = Box::into_raw(Box::new(slint::compile_result_from_source(source, path)));
+    ring_ret_managed_cpointer!(
+        p,
+        ptr,
+        slint::SLINT_COMPILATION_TYPE,
+        free_slint_compilation
+    );
+});
+
+ring_func!(ring_slint_result_has_errors, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(compiled) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintCompilationResult,
+        slint::SLINT_COMPILATION_TYPE
+    ) {
+        let result = slint::result_has_errors(compiled);
+        ring_ret_number!(p, if result { 1.0 } else { 0.0 });
+    } else {
+        ring_error!(p, "Invalid SlintCompilationResult pointer");
+    }
+});
+
+ring_func!(ring_slint_result_diagnostics, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(compiled) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintCompilationResult,
+        slint::SLINT_COMPILATION_TYPE
+    ) {
+        let list = ring_api_newlist(p);
+        for diag in slint::result_diagnostics(compiled) {
+            let record = ring_list_newlist(list);
+            add_diagnostic_fields(record, &diag);
+        }
+        ring_ret_list!(p, list);
+    } else {
+        ring_error!(p, "Invalid SlintCompilationResult pointer");
+    }
+});
+
+ring_func!(ring_slint_result_component_names, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(compiled) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintCompilationResult,
+        slint::SLINT_COMPILATION_TYPE
+    ) {
+        let list = ring_api_newlist(p);
+        for name in slint::result_component_names(compiled) {
+            ring_list_addstring_str(list, &name);
+        }
+        ring_ret_list!(p, list);
+    } else {
+        ring_error!(p, "Invalid SlintCompilationResult pointer");
+    }
+});
+
+ring_func!(ring_slint_result_component, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    if let Some(compiled) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintCompilationResult,
+        slint::SLINT_COMPILATION_TYPE
+    ) {
+        let name = ring_get_string!(p, 2);
+        match slint::result_component(compiled, name) {
+            Ok(component) => {
+                let ptr = Box::into_raw(Box::new(component));
+                ring_ret_managed_cpointer!(
+                    p,
+                    ptr,
+                    slint::SLINT_COMPONENT_TYPE,
+                    free_slint_component
+                );
+            }
+            Err(e) => {
+                ring_error!(p, &e);
+            }
+        }
+    } else {
+        ring_error!(p, "Invalid SlintCompilationResult pointer");
+    }
+});
+
+ring_func!(ring_slint_result_types, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(compiled) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintCompilationResult,
+        slint::SLINT_COMPILATION_TYPE
+    ) {
+        let list = ring_api_newlist(p);
+        for (name, kind) in slint::result_types(compiled) {
+            let sublist = ring_list_newlist(list);
+            ring_list_addstring_str(sublist, &name);
+            ring_list_addstring_str(sublist, kind);
+        }
+        ring_ret_list!(p, list);
+    } else {
+        ring_error!(p, "Invalid SlintCompilationResult pointer");
+    }
+});
+
+ring_func!(ring_slint_result_enums, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(compiled) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintCompilationResult,
+        slint::SLINT_COMPILATION_TYPE
+    ) {
+        let list = ring_api_newlist(p);
+        for ty in slint::result_enums(compiled) {
+            slint::add_type_description(ring_list_newlist(list), &ty);
+        }
+        ring_ret_list!(p, list);
+    } else {
+        ring_error!(p, "Invalid SlintCompilationResult pointer");
+    }
+});
+
+ring_func!(ring_slint_result_structs, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(compiled) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintCompilationResult,
+        slint::SLINT_COMPILATION_TYPE
+    ) {
+        let list = ring_api_newlist(p);
+        for ty in slint::result_structs(compiled) {
+            slint::add_type_description(ring_list_newlist(list), &ty);
+        }
+        ring_ret_list!(p, list);
+    } else {
+        ring_error!(p, "Invalid SlintCompilationResult pointer");
+    }
+});
+
 ring_func!(ring_slint_create, |p| {
     ring_check_paracount!(p, 1);
     ring_check_cpointer!(p, 1);
@@ -226,21 +569,28 @@ ring_func!(ring_slint_set, |p| {
         slint::SLINT_INSTANCE_TYPE
     ) {
         let prop_name = ring_get_string!(p, 2);
-        let value = slint::ring_list_to_slint_value(p, 3);
+        let prop_type = slint::property_type(&wrapper.instance.definition(), None, prop_name);
+        let value = match slint::ring_param_to_value(p, 3, prop_type.as_ref()) {
+            Ok(value) => value,
+            Err(e) => {
+                ring_error!(p, &format!("Cannot set property '{}': {}", prop_name, e));
+                return;
+            }
+        };
 
-        if let Err(e) = slint::instance_set_property(&wrapper.instance, prop_name, value) {
-            ring_error!(p, &e);
+        match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+            Ok(()) => wrapper.record_property(prop_name),
+            Err(e) => ring_error!(p, &e),
         }
     } else {
         ring_error!(p, "Invalid SlintInstance pointer");
     }
 });
 
-ring_func!(ring_slint_set_bool, |p| {
-    ring_check_paracount!(p, 3);
+ring_func!(ring_slint_get_json, |p| {
+    ring_check_paracount!(p, 2);
     ring_check_cpointer!(p, 1);
     ring_check_string!(p, 2);
-    ring_check_number!(p, 3);
 
     if let Some(wrapper) = ring_get_pointer!(
         p,
@@ -249,17 +599,16 @@ ring_func!(ring_slint_set_bool, |p| {
         slint::SLINT_INSTANCE_TYPE
     ) {
         let prop_name = ring_get_string!(p, 2);
-        let value = Value::Bool(ring_get_int!(p, 3) != 0);
-
-        if let Err(e) = slint::instance_set_property(&wrapper.instance, prop_name, value) {
-            ring_error!(p, &e);
+        match slint::instance_get_property(&wrapper.instance, prop_name) {
+            Ok(value) => ring_ret_string!(p, &slint::value_to_json_string(&value)),
+            Err(e) => ring_error!(p, &e),
         }
     } else {
         ring_error!(p, "Invalid SlintInstance pointer");
     }
 });
 
-ring_func!(ring_slint_set_image, |p| {
+ring_func!(ring_slint_set_json, |p| {
     ring_check_paracount!(p, 3);
     ring_check_cpointer!(p, 1);
     ring_check_string!(p, 2);
@@ -272,28 +621,29 @@ ring_func!(ring_slint_set_image, |p| {
         slint::SLINT_INSTANCE_TYPE
     ) {
         let prop_name = ring_get_string!(p, 2);
-        let path = ring_get_string!(p, 3);
-
-        match slint::ring_string_to_image(path) {
-            Ok(value) => {
-                if let Err(e) = slint::instance_set_property(&wrapper.instance, prop_name, value) {
-                    ring_error!(p, &e);
-                }
-            }
+        let prop_type = slint::property_type(&wrapper.instance.definition(), None, prop_name);
+        let value = match slint::json_string_to_value(ring_get_string!(p, 3), prop_type.as_ref()) {
+            Ok(value) => value,
             Err(e) => {
-                ring_error!(p, &e);
+                ring_error!(p, &format!("Cannot set property '{}': {}", prop_name, e));
+                return;
             }
+        };
+
+        match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+            Ok(()) => wrapper.record_property(prop_name),
+            Err(e) => ring_error!(p, &e),
         }
     } else {
         ring_error!(p, "Invalid SlintInstance pointer");
     }
 });
 
-ring_func!(ring_slint_set_string, |p| {
+ring_func!(ring_slint_set_bool, |p| {
     ring_check_paracount!(p, 3);
     ring_check_cpointer!(p, 1);
     ring_check_string!(p, 2);
-    ring_check_string!(p, 3);
+    ring_check_number!(p, 3);
 
     if let Some(wrapper) = ring_get_pointer!(
         p,
@@ -302,18 +652,269 @@ ring_func!(ring_slint_set_string, |p| {
         slint::SLINT_INSTANCE_TYPE
     ) {
         let prop_name = ring_get_string!(p, 2);
-        let s = ring_get_string!(p, 3);
-        let value = Value::String(slint_interpreter::SharedString::from(s));
+        let value = Value::Bool(ring_get_int!(p, 3) != 0);
 
-        if let Err(e) = slint::instance_set_property(&wrapper.instance, prop_name, value) {
-            ring_error!(p, &e);
+        match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+            Ok(()) => wrapper.record_property(prop_name),
+            Err(e) => ring_error!(p, &e),
         }
     } else {
         ring_error!(p, "Invalid SlintInstance pointer");
     }
 });
 
-ring_func!(ring_slint_set_number, |p| {
+ring_func!(ring_slint_set_image, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+
+        let image = if ring_api_isstring(p, 3) {
+            slint::ring_string_to_image(ring_get_string!(p, 3))
+        } else if ring_api_iscpointer(p, 3) {
+            match ring_get_pointer!(p, 3, slint::SlintValueWrapper, slint::SLINT_VALUE_TYPE) {
+                Some(image) if matches!(image.value, Value::Image(_)) => Ok(image.value.clone()),
+                _ => Err("slint_set_image() expects an image SlintValue".to_string()),
+            }
+        } else {
+            Err("slint_set_image() expects a file path or an image SlintValue".to_string())
+        };
+
+        match image {
+            Ok(value) => match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+                Ok(()) => wrapper.record_property(prop_name),
+                Err(e) => ring_error!(p, &e),
+            },
+            Err(e) => {
+                ring_error!(p, &e);
+            }
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_image_from_bytes, |p| {
+    let paracount = ring_api_paracount(p);
+    if !(1..=2).contains(&paracount) {
+        ring_error!(
+            p,
+            "slint_image_from_bytes(bytes, [format]) expects 1-2 parameters"
+        );
+        return;
+    }
+    ring_check_string!(p, 1);
+
+    let bytes = ring_get_bytes(p, 1);
+    let format = if paracount == 2 {
+        ring_check_string!(p, 2);
+        Some(ring_get_string!(p, 2))
+    } else {
+        None
+    };
+
+    match slint::image_from_bytes(bytes, format) {
+        Ok(image) => ring_ret_slint_value(p, Value::Image(image)),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_image_from_rgba, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_string!(p, 1);
+    ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
+
+    let pixels = ring_get_bytes(p, 1);
+    // Negative sizes become 0, which the pixel check rejects.
+    let width = u32::try_from(ring_get_int!(p, 2)).unwrap_or(0);
+    let height = u32::try_from(ring_get_int!(p, 3)).unwrap_or(0);
+    match slint::image_from_rgba8(pixels, width, height) {
+        Ok(image) => ring_ret_slint_value(p, Value::Image(image)),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_image_from_rgb, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_string!(p, 1);
+    ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
+
+    let pixels = ring_get_bytes(p, 1);
+    // Negative sizes become 0, which the pixel check rejects.
+    let width = u32::try_from(ring_get_int!(p, 2)).unwrap_or(0);
+    let height = u32::try_from(ring_get_int!(p, 3)).unwrap_or(0);
+    match slint::image_from_rgb8(pixels, width, height) {
+        Ok(image) => ring_ret_slint_value(p, Value::Image(image)),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_image_info, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+        match slint::get_image_property(&wrapper.instance, prop_name) {
+            Ok(image) => {
+                let (width, height) = slint::image_size(&image);
+                let path = image
+                    .path()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                let list = ring_api_newlist(p);
+                let pair = ring_list_newlist(list);
+                ring_list_addstring_str(pair, "width");
+                ring_list_addint(pair, width as i32);
+                let pair = ring_list_newlist(list);
+                ring_list_addstring_str(pair, "height");
+                ring_list_addint(pair, height as i32);
+                let pair = ring_list_newlist(list);
+                ring_list_addstring_str(pair, "path");
+                ring_list_addstring_str(pair, &path);
+                ring_ret_list!(p, list);
+            }
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_image_data, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+        let format = ring_get_string!(p, 3).to_lowercase();
+        let data =
+            slint::get_image_property(&wrapper.instance, prop_name).and_then(|image| match format
+                .as_str()
+            {
+                "rgba" => slint::image_to_rgba8(&image),
+                "png" => slint::image_to_png(&image),
+                other => Err(format!(
+                    "Unsupported image data format '{}' (expected \"rgba\" or \"png\")",
+                    other
+                )),
+            });
+        match data {
+            Ok(bytes) => ring_api_retstring2(p, &bytes),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_value_bool, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let flag = ring_api_getnumber(p, 1) != 0.0;
+    ring_ret_slint_value(p, Value::Bool(flag));
+});
+
+ring_func!(ring_slint_value_enum, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_string!(p, 1);
+    ring_check_string!(p, 2);
+
+    let enum_name = ring_get_string!(p, 1);
+    let value = ring_get_string!(p, 2);
+    let value = value
+        .strip_prefix(enum_name)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .unwrap_or(value);
+    ring_ret_slint_value(
+        p,
+        Value::EnumerationValue(enum_name.to_string(), value.to_string()),
+    );
+});
+
+ring_func!(ring_slint_value_color, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_string!(p, 1);
+
+    let color = ring_get_string!(p, 1);
+    match slint::parse_color_value(color) {
+        Some(value) => ring_ret_slint_value(p, value),
+        None => ring_error!(p, &format!("Invalid color '{}'", color)),
+    }
+});
+
+ring_func!(ring_slint_value_image, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_string!(p, 1);
+
+    match slint::ring_string_to_image(ring_get_string!(p, 1)) {
+        Ok(value) => ring_ret_slint_value(p, value),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_value_length, |p| {
+    ring_ret_unit_value(p, "length", slint::parse_length);
+});
+
+ring_func!(ring_slint_value_duration, |p| {
+    ring_ret_unit_value(p, "duration", slint::parse_duration);
+});
+
+ring_func!(ring_slint_value_angle, |p| {
+    ring_ret_unit_value(p, "angle", slint::parse_angle);
+});
+
+ring_func!(ring_slint_set_string, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+        let s = ring_get_string!(p, 3);
+        let value = Value::String(slint_interpreter::SharedString::from(s));
+
+        match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+            Ok(()) => wrapper.record_property(prop_name),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_set_number, |p| {
     ring_check_paracount!(p, 3);
     ring_check_cpointer!(p, 1);
     ring_check_string!(p, 2);
@@ -328,8 +929,9 @@ ring_func!(ring_slint_set_number, |p| {
         let prop_name = ring_get_string!(p, 2);
         let value = Value::Number(ring_api_getnumber(p, 3));
 
-        if let Err(e) = slint::instance_set_property(&wrapper.instance, prop_name, value) {
-            ring_error!(p, &e);
+        match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+            Ok(()) => wrapper.record_property(prop_name),
+            Err(e) => ring_error!(p, &e),
         }
     } else {
         ring_error!(p, "Invalid SlintInstance pointer");
@@ -349,20 +951,21 @@ ring_func!(ring_slint_set_color, |p| {
         slint::SLINT_INSTANCE_TYPE
     ) {
         let prop_name = ring_get_string!(p, 2);
-        let hex = ring_get_string!(p, 3);
+        let color = ring_get_string!(p, 3);
 
-        match slint::parse_hex_color_value(hex) {
+        match slint::parse_color_value(color) {
             Some(value) => {
-                if let Err(e) = slint::instance_set_property(&wrapper.instance, prop_name, value) {
-                    ring_error!(p, &e);
+                match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+                    Ok(()) => wrapper.record_property(prop_name),
+                    Err(e) => ring_error!(p, &e),
                 }
             }
             None => {
                 ring_error!(
                     p,
                     &format!(
-                        "Invalid color format '{}'. Expected hex color like #RRGGBB or #RRGGBBAA",
-                        hex
+                        "Invalid color '{}'. Expected #RGB, #RRGGBB[AA], rgb(), rgba(), hsl(), hsla() or a CSS color name",
+                        color
                     )
                 );
             }
@@ -372,6 +975,23 @@ ring_func!(ring_slint_set_color, |p| {
     }
 });
 
+ring_func!(ring_slint_color_format, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_string!(p, 1);
+
+    match ring_get_string!(p, 1) {
+        "hex" => slint::set_colors_as_lists(false),
+        "list" => slint::set_colors_as_lists(true),
+        other => ring_error!(
+            p,
+            &format!(
+                "Unknown color format '{}'. Expected \"hex\" or \"list\"",
+                other
+            )
+        ),
+    }
+});
+
 ring_func!(ring_slint_set_enum, |p| {
     ring_check_paracount!(p, 3);
     ring_check_cpointer!(p, 1);
@@ -392,8 +1012,9 @@ ring_func!(ring_slint_set_enum, |p| {
             let variant = &s[dot_pos + 1..];
             let value = Value::EnumerationValue(name.to_string(), variant.to_string());
 
-            if let Err(e) = slint::instance_set_property(&wrapper.instance, prop_name, value) {
-                ring_error!(p, &e);
+            match slint::instance_set_property(&wrapper.instance, prop_name, value) {
+                Ok(()) => wrapper.record_property(prop_name),
+                Err(e) => ring_error!(p, &e),
             }
         } else {
             ring_error!(
@@ -407,7 +1028,14 @@ ring_func!(ring_slint_set_enum, |p| {
 });
 
 ring_func!(ring_slint_on, |p| {
-    ring_check_paracount!(p, 3);
+    let paracount = ring_api_paracount(p);
+    if !(3..=4).contains(&paracount) {
+        ring_error!(
+            p,
+            "slint_on(window, callback, func, [params]) expects 3-4 parameters"
+        );
+        return;
+    }
     ring_check_cpointer!(p, 1);
     ring_check_string!(p, 2);
     ring_check_string!(p, 3);
@@ -419,12 +1047,22 @@ ring_func!(ring_slint_on, |p| {
         slint::SLINT_INSTANCE_TYPE
     ) {
         let callback_name = ring_get_string!(p, 2);
-        let ring_func = ring_get_string!(p, 3).to_string();
+        let Some(handler) = ring_get_handler(p, 3, paracount) else {
+            return;
+        };
 
-        if let Err(e) =
-            slint::set_callback(&wrapper.instance, callback_name, p as RingVM, ring_func)
-        {
-            ring_error!(p, &e);
+        match slint::set_callback(
+            &wrapper.instance,
+            callback_name,
+            p as RingVM,
+            handler.clone(),
+        ) {
+            Ok(()) => {
+                if let Some(old) = wrapper.record_callback(callback_name, &handler) {
+                    old.release(p as RingVM);
+                }
+            }
+            Err(e) => ring_error!(p, &e),
         }
     } else {
         ring_error!(p, "Invalid SlintInstance pointer");
@@ -453,7 +1091,15 @@ ring_func!(ring_slint_invoke, |p| {
         let callback_name = ring_get_string!(p, 2);
 
         let args = if paracount == 3 && ring_api_islist(p, 3) {
-            slint::ring_list_to_args(ring_api_getlist(p, 3))
+            let arg_types =
+                slint::callback_arg_types(&wrapper.instance.definition(), None, callback_name);
+            match slint::ring_list_to_typed_args(ring_api_getlist(p, 3), arg_types.as_deref()) {
+                Ok(args) => args,
+                Err(e) => {
+                    ring_error!(p, &format!("Cannot invoke '{}': {}", callback_name, e));
+                    return;
+                }
+            }
         } else {
             Vec::new()
         };
@@ -481,22 +1127,747 @@ ring_func!(ring_slint_callback_arg, |p| {
         return;
     }
 
-    if let Some(value) = slint::get_callback_arg(index - 1) {
-        slint::slint_value_to_ring(p, &value);
-    } else {
-        ring_ret_number!(p, 0);
+    if let Some(value) = slint::get_callback_arg(index - 1) {
+        slint::slint_value_to_ring(p, &value);
+    } else {
+        ring_ret_number!(p, 0);
+    }
+});
+
+ring_func!(ring_slint_callback_args_count, |p| {
+    ring_check_paracount!(p, 0);
+    ring_ret_number!(p, slint::get_callback_args_count() as f64);
+});
+
+ring_func!(ring_slint_callback_return, |p| {
+    ring_check_paracount!(p, 1);
+
+    let Some(return_type) = slint::running_callback_return_type() else {
+        ring_error!(
+            p,
+            "slint_callback_return() can only be used inside a callback"
+        );
+        return;
+    };
+    match slint::ring_param_to_value(p, 1, Some(&return_type)) {
+        Ok(value) => {
+            slint::set_callback_return(value);
+        }
+        // The NULL a function without `return` gives leaves the frame without
+        // a value, so Slint gets the type's default.
+        Err(_) if ring_api_isstring(p, 1) && ring_get_string!(p, 1).is_empty() => {}
+        Err(e) => ring_error!(p, &format!("Cannot return from callback: {}", e)),
+    }
+});
+
+ring_func!(ring_slint_global_get, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let prop_name = ring_get_string!(p, 3);
+
+        match slint::get_global_property(&wrapper.instance, global_name, prop_name) {
+            Ok(value) => {
+                slint::slint_value_to_ring(p, &value);
+            }
+            Err(e) => {
+                ring_error!(p, &e);
+            }
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_global_set, |p| {
+    ring_check_paracount!(p, 4);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let prop_name = ring_get_string!(p, 3);
+        let prop_type =
+            slint::property_type(&wrapper.instance.definition(), Some(global_name), prop_name);
+        let value = match slint::ring_param_to_value(p, 4, prop_type.as_ref()) {
+            Ok(value) => value,
+            Err(e) => {
+                ring_error!(
+                    p,
+                    &format!("Cannot set property '{}.{}': {}", global_name, prop_name, e)
+                );
+                return;
+            }
+        };
+
+        match slint::set_global_property(&wrapper.instance, global_name, prop_name, value) {
+            Ok(()) => wrapper.record_global_property(global_name, prop_name),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_global_get_json, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let prop_name = ring_get_string!(p, 3);
+
+        match slint::get_global_property(&wrapper.instance, global_name, prop_name) {
+            Ok(value) => ring_ret_string!(p, &slint::value_to_json_string(&value)),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_global_set_json, |p| {
+    ring_check_paracount!(p, 4);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+    ring_check_string!(p, 4);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let prop_name = ring_get_string!(p, 3);
+        let prop_type =
+            slint::property_type(&wrapper.instance.definition(), Some(global_name), prop_name);
+        let value = match slint::json_string_to_value(ring_get_string!(p, 4), prop_type.as_ref()) {
+            Ok(value) => value,
+            Err(e) => {
+                ring_error!(
+                    p,
+                    &format!("Cannot set property '{}.{}': {}", global_name, prop_name, e)
+                );
+                return;
+            }
+        };
+
+        match slint::set_global_property(&wrapper.instance, global_name, prop_name, value) {
+            Ok(()) => wrapper.record_global_property(global_name, prop_name),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_global_on, |p| {
+    let paracount = ring_api_paracount(p);
+    if !(4..=5).contains(&paracount) {
+        ring_error!(
+            p,
+            "slint_global_on(window, global, callback, func, [params]) expects 4-5 parameters"
+        );
+        return;
+    }
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+    ring_check_string!(p, 4);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let callback_name = ring_get_string!(p, 3);
+        let Some(handler) = ring_get_handler(p, 4, paracount) else {
+            return;
+        };
+
+        match slint::set_global_callback(
+            &wrapper.instance,
+            global_name,
+            callback_name,
+            p as RingVM,
+            handler.clone(),
+        ) {
+            Ok(()) => {
+                if let Some(old) =
+                    wrapper.record_global_callback(global_name, callback_name, &handler)
+                {
+                    old.release(p as RingVM);
+                }
+            }
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_global_invoke, |p| {
+    let paracount = ring_api_paracount(p);
+    if !(3..=4).contains(&paracount) {
+        ring_error!(
+            p,
+            "slint_global_invoke(window, global, callback, [args]) expects 3-4 parameters"
+        );
+        return;
+    }
+
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let callback_name = ring_get_string!(p, 3);
+
+        let args = if paracount == 4 && ring_api_islist(p, 4) {
+            let arg_types = slint::callback_arg_types(
+                &wrapper.instance.definition(),
+                Some(global_name),
+                callback_name,
+            );
+            match slint::ring_list_to_typed_args(ring_api_getlist(p, 4), arg_types.as_deref()) {
+                Ok(args) => args,
+                Err(e) => {
+                    ring_error!(
+                        p,
+                        &format!("Cannot invoke '{}.{}': {}", global_name, callback_name, e)
+                    );
+                    return;
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        match slint::invoke_global(&wrapper.instance, global_name, callback_name, &args) {
+            Ok(result) => {
+                slint::slint_value_to_ring(p, &result);
+            }
+            Err(e) => {
+                ring_error!(p, &e);
+            }
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_hot_reload, |p| {
+    let paracount = ring_api_paracount(p);
+    if !(2..=3).contains(&paracount) {
+        ring_error!(
+            p,
+            "slint_hot_reload(window, file, [interval_ms]) expects 2-3 parameters"
+        );
+        return;
+    }
+
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    let interval_ms = if paracount == 3 {
+        ring_check_number!(p, 3);
+        ring_get_int!(p, 3) as u64
+    } else {
+        500
+    };
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let path = ring_get_string!(p, 2);
+        let wrapper_ptr =
+            wrapper as *const slint::SlintInstanceWrapper as *mut slint::SlintInstanceWrapper;
+        match slint::hot_reload_start(wrapper_ptr, path, interval_ms) {
+            Ok(reload_id) => ring_ret_number!(p, reload_id as f64),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_hot_reload_stop, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let reload_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::hot_reload_stop(reload_id) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_hot_reload_diagnostics, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let reload_id = ring_get_int!(p, 1) as u32;
+    match slint::hot_reload_diagnostics(reload_id) {
+        Ok(diagnostics) => {
+            let list = ring_api_newlist(p);
+            for diag in diagnostics {
+                let record = ring_list_newlist(list);
+                add_diagnostic_fields(record, &diag);
+            }
+            ring_ret_list!(p, list);
+        }
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_watch, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+        let handler = ring_get_string!(p, 3).to_string();
+        match slint::watch_property(wrapper, None, prop_name, p as RingVM, handler) {
+            Ok(watch_id) => ring_ret_number!(p, watch_id as f64),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_global_watch, |p| {
+    ring_check_paracount!(p, 4);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+    ring_check_string!(p, 4);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let prop_name = ring_get_string!(p, 3);
+        let handler = ring_get_string!(p, 4).to_string();
+        match slint::watch_property(wrapper, Some(global_name), prop_name, p as RingVM, handler) {
+            Ok(watch_id) => ring_ret_number!(p, watch_id as f64),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_unwatch, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let watch_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::unwatch(watch_id) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_timer_start, |p| {
+    let paracount = ring_api_paracount(p);
+    if !(2..=3).contains(&paracount) {
+        ring_error!(
+            p,
+            "slint_timer_start(interval_ms, callback, [repeated]) expects 2-3 parameters"
+        );
+        return;
+    }
+
+    ring_check_number!(p, 1);
+    ring_check_string!(p, 2);
+
+    let interval_ms = ring_get_int!(p, 1) as u64;
+    let callback_name = ring_get_string!(p, 2).to_string();
+
+    let repeated = if paracount == 3 {
+        ring_check_number!(p, 3);
+        ring_get_int!(p, 3) != 0
+    } else {
+        true
+    };
+
+    let timer_id = slint::timer_start(interval_ms, repeated, p as RingVM, callback_name);
+    ring_ret_number!(p, timer_id as f64);
+});
+
+ring_func!(ring_slint_timer_stop, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let timer_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::timer_stop(timer_id) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_timer_running, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let timer_id = ring_get_int!(p, 1) as u32;
+    match slint::timer_running(timer_id) {
+        Ok(running) => ring_ret_number!(p, if running { 1.0 } else { 0.0 }),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_timer_restart, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let timer_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::timer_restart(timer_id) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_timer_set_interval, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+
+    let timer_id = ring_get_int!(p, 1) as u32;
+    let interval_ms = ring_get_int!(p, 2) as u64;
+    if let Err(e) = slint::timer_set_interval(timer_id, interval_ms) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_create, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+        match slint::model_create(&wrapper.instance, prop_name) {
+            Ok(model_id) => {
+                wrapper.record_property(prop_name);
+                ring_ret_number!(p, model_id as f64);
+            }
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_model_adopt, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+        match slint::model_adopt(&wrapper.instance, prop_name) {
+            Ok(model_id) => {
+                wrapper.record_property(prop_name);
+                ring_ret_number!(p, model_id as f64);
+            }
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_model_rows, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let prop_name = ring_get_string!(p, 2);
+        match slint::model_property(&wrapper.instance, prop_name) {
+            Ok(model) => slint::slint_value_to_ring(p, &Value::Model(model)),
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_model_create_global, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_cpointer!(p, 1);
+    ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+
+    if let Some(wrapper) = ring_get_pointer!(
+        p,
+        1,
+        slint::SlintInstanceWrapper,
+        slint::SLINT_INSTANCE_TYPE
+    ) {
+        let global_name = ring_get_string!(p, 2);
+        let prop_name = ring_get_string!(p, 3);
+        match slint::model_create_global(&wrapper.instance, global_name, prop_name) {
+            Ok(model_id) => {
+                wrapper.record_global_property(global_name, prop_name);
+                ring_ret_number!(p, model_id as f64);
+            }
+            Err(e) => ring_error!(p, &e),
+        }
+    } else {
+        ring_error!(p, "Invalid SlintInstance pointer");
+    }
+});
+
+ring_func!(ring_slint_model_create_nested, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+    ring_check_string!(p, 3);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let index = ring_get_int!(p, 2) as usize;
+    let field = ring_get_string!(p, 3);
+    match slint::model_create_nested(model_id, index, field) {
+        Ok(nested_id) => ring_ret_number!(p, nested_id as f64),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_model_push, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_number!(p, 1);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let result = slint::ring_param_to_row_value(p, 2, model_id)
+        .and_then(|value| slint::model_push(model_id, value));
+    if let Err(e) = result {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_remove, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let index = ring_get_int!(p, 2) as usize;
+    if let Err(e) = slint::model_remove(model_id, index) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_set, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let index = ring_get_int!(p, 2) as usize;
+    let result = slint::ring_param_to_row_value(p, 3, model_id)
+        .and_then(|value| slint::model_set(model_id, index, value));
+    if let Err(e) = result {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_count, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    match slint::model_count(model_id) {
+        Ok(count) => ring_ret_number!(p, count as f64),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
+ring_func!(ring_slint_model_clear, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::model_clear(model_id) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_insert, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let index = ring_get_int!(p, 2) as usize;
+    let result = slint::ring_param_to_row_value(p, 3, model_id)
+        .and_then(|value| slint::model_insert(model_id, index, value));
+    if let Err(e) = result {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_push_many, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_number!(p, 1);
+    if !ring_api_islist(p, 2) {
+        ring_error!(p, "Expected a list of rows");
+        return;
+    }
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let list = ring_api_getlist(p, 2);
+    let result = slint::ring_list_to_model_rows(list, model_id)
+        .and_then(|rows| slint::model_push_many(model_id, rows));
+    if let Err(e) = result {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_replace, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_number!(p, 1);
+    if !ring_api_islist(p, 2) {
+        ring_error!(p, "Expected a list of rows");
+        return;
+    }
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let list = ring_api_getlist(p, 2);
+    let result = slint::ring_list_to_model_rows(list, model_id)
+        .and_then(|rows| slint::model_replace(model_id, rows));
+    if let Err(e) = result {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_remove_range, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let index = ring_get_int!(p, 2) as usize;
+    let count = ring_get_int!(p, 3) as usize;
+    if let Err(e) = slint::model_remove_range(model_id, index, count) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_swap, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let a = ring_get_int!(p, 2) as usize;
+    let b = ring_get_int!(p, 3) as usize;
+    if let Err(e) = slint::model_swap(model_id, a, b) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_move, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let from = ring_get_int!(p, 2) as usize;
+    let to = ring_get_int!(p, 3) as usize;
+    if let Err(e) = slint::model_move(model_id, from, to) {
+        ring_error!(p, &e);
+    }
+});
+
+ring_func!(ring_slint_model_destroy, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::model_destroy(model_id) {
+        ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_callback_args_count, |p| {
-    ring_check_paracount!(p, 0);
-    ring_ret_number!(p, slint::get_callback_args_count() as f64);
+ring_func!(ring_slint_model_on_change, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_number!(p, 1);
+    ring_check_string!(p, 2);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    let handler = ring_get_string!(p, 2);
+    let handler = (!handler.trim().is_empty()).then_some(handler);
+    if let Err(e) = slint::model_on_change(model_id, p as RingVM, handler) {
+        ring_error!(p, &e);
+    }
 });
 
-ring_func!(ring_slint_global_get, |p| {
+ring_func!(ring_slint_model_view_create, |p| {
     ring_check_paracount!(p, 3);
     ring_check_cpointer!(p, 1);
-    ring_check_string!(p, 2);
+    ring_check_number!(p, 2);
     ring_check_string!(p, 3);
 
     if let Some(wrapper) = ring_get_pointer!(
@@ -505,194 +1876,146 @@ ring_func!(ring_slint_global_get, |p| {
         slint::SlintInstanceWrapper,
         slint::SLINT_INSTANCE_TYPE
     ) {
-        let global_name = ring_get_string!(p, 2);
+        let model_id = ring_get_int!(p, 2) as u32;
         let prop_name = ring_get_string!(p, 3);
-
-        match slint::get_global_property(&wrapper.instance, global_name, prop_name) {
-            Ok(value) => {
-                slint::slint_value_to_ring(p, &value);
-            }
-            Err(e) => {
-                ring_error!(p, &e);
+        match slint::model_view_create(&wrapper.instance, model_id, prop_name) {
+            Ok(view_id) => {
+                wrapper.record_property(prop_name);
+                ring_ret_number!(p, view_id as f64);
             }
+            Err(e) => ring_error!(p, &e),
         }
     } else {
         ring_error!(p, "Invalid SlintInstance pointer");
     }
 });
 
-ring_func!(ring_slint_global_set, |p| {
-    ring_check_paracount!(p, 4);
-    ring_check_cpointer!(p, 1);
+ring_func!(ring_slint_model_view_sort_by, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
     ring_check_string!(p, 2);
-    ring_check_string!(p, 3);
-
-    if let Some(wrapper) = ring_get_pointer!(
-        p,
-        1,
-        slint::SlintInstanceWrapper,
-        slint::SLINT_INSTANCE_TYPE
-    ) {
-        let global_name = ring_get_string!(p, 2);
-        let prop_name = ring_get_string!(p, 3);
-        let value = slint::ring_list_to_slint_value(p, 4);
+    ring_check_number!(p, 3);
 
-        if let Err(e) = slint::set_global_property(&wrapper.instance, global_name, prop_name, value)
-        {
-            ring_error!(p, &e);
-        }
-    } else {
-        ring_error!(p, "Invalid SlintInstance pointer");
+    let view_id = ring_get_int!(p, 1) as u32;
+    let field = ring_get_string!(p, 2);
+    let descending = ring_get_int!(p, 3) != 0;
+    if let Err(e) = slint::model_view_sort_by(view_id, field, descending) {
+        ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_global_on, |p| {
-    ring_check_paracount!(p, 4);
-    ring_check_cpointer!(p, 1);
+ring_func!(ring_slint_model_view_sort_func, |p| {
+    ring_check_paracount!(p, 2);
+    ring_check_number!(p, 1);
     ring_check_string!(p, 2);
-    ring_check_string!(p, 3);
-    ring_check_string!(p, 4);
-
-    if let Some(wrapper) = ring_get_pointer!(
-        p,
-        1,
-        slint::SlintInstanceWrapper,
-        slint::SLINT_INSTANCE_TYPE
-    ) {
-        let global_name = ring_get_string!(p, 2);
-        let callback_name = ring_get_string!(p, 3);
-        let ring_func = ring_get_string!(p, 4).to_string();
 
-        if let Err(e) = slint::set_global_callback(
-            &wrapper.instance,
-            global_name,
-            callback_name,
-            p as RingVM,
-            ring_func,
-        ) {
-            ring_error!(p, &e);
-        }
-    } else {
-        ring_error!(p, "Invalid SlintInstance pointer");
+    let view_id = ring_get_int!(p, 1) as u32;
+    let handler = ring_get_string!(p, 2);
+    let handler = (!handler.trim().is_empty()).then_some(handler);
+    if let Err(e) = slint::model_view_sort_func(view_id, p as RingVM, handler) {
+        ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_global_invoke, |p| {
-    let paracount = ring_api_paracount(p);
-    if !(3..=4).contains(&paracount) {
-        ring_error!(
-            p,
-            "slint_global_invoke(window, global, callback, [args]) expects 3-4 parameters"
-        );
-        return;
-    }
-
-    ring_check_cpointer!(p, 1);
+ring_func!(ring_slint_model_view_filter, |p| {
+    ring_check_paracount!(p, 4);
+    ring_check_number!(p, 1);
     ring_check_string!(p, 2);
     ring_check_string!(p, 3);
 
-    if let Some(wrapper) = ring_get_pointer!(
-        p,
-        1,
-        slint::SlintInstanceWrapper,
-        slint::SLINT_INSTANCE_TYPE
-    ) {
-        let global_name = ring_get_string!(p, 2);
-        let callback_name = ring_get_string!(p, 3);
-
-        let args = if paracount == 4 && ring_api_islist(p, 4) {
-            slint::ring_list_to_args(ring_api_getlist(p, 4))
-        } else {
-            Vec::new()
-        };
-
-        match slint::invoke_global(&wrapper.instance, global_name, callback_name, &args) {
-            Ok(result) => {
-                slint::slint_value_to_ring(p, &result);
-            }
-            Err(e) => {
-                ring_error!(p, &e);
-            }
-        }
-    } else {
-        ring_error!(p, "Invalid SlintInstance pointer");
+    let view_id = ring_get_int!(p, 1) as u32;
+    let field = ring_get_string!(p, 2);
+    let op = ring_get_string!(p, 3);
+    let value = slint::ring_param_to_model_value(p, 4);
+    if let Err(e) = slint::model_view_filter(view_id, field, op, value) {
+        ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_timer_start, |p| {
-    let paracount = ring_api_paracount(p);
-    if !(2..=3).contains(&paracount) {
-        ring_error!(
-            p,
-            "slint_timer_start(interval_ms, callback, [repeated]) expects 2-3 parameters"
-        );
-        return;
-    }
-
+ring_func!(ring_slint_model_view_filter_func, |p| {
+    ring_check_paracount!(p, 2);
     ring_check_number!(p, 1);
     ring_check_string!(p, 2);
 
-    let interval_ms = ring_get_int!(p, 1) as u64;
-    let callback_name = ring_get_string!(p, 2).to_string();
+    let view_id = ring_get_int!(p, 1) as u32;
+    let handler = ring_get_string!(p, 2);
+    let handler = (!handler.trim().is_empty()).then_some(handler);
+    if let Err(e) = slint::model_view_filter_func(view_id, p as RingVM, handler) {
+        ring_error!(p, &e);
+    }
+});
 
-    let repeated = if paracount == 3 {
-        ring_check_number!(p, 3);
-        ring_get_int!(p, 3) != 0
-    } else {
-        true
-    };
+ring_func!(ring_slint_model_view_search, |p| {
+    ring_check_paracount!(p, 3);
+    ring_check_number!(p, 1);
+    ring_check_string!(p, 2);
+    if !ring_api_islist(p, 3) {
+        ring_error!(p, "Expected a list of field names");
+        return;
+    }
 
-    let timer_id = slint::timer_start(interval_ms, repeated, p as RingVM, callback_name);
-    ring_ret_number!(p, timer_id as f64);
+    let view_id = ring_get_int!(p, 1) as u32;
+    let text = ring_get_string!(p, 2);
+    let list = ring_api_getlist(p, 3);
+    let fields = (1..=ring_list_getsize(list))
+        .filter(|&i| ring_list_isstring(list, i))
+        .map(|i| ring_list_getstring_str(list, i).to_string())
+        .collect();
+    if let Err(e) = slint::model_view_search(view_id, text, fields) {
+        ring_error!(p, &e);
+    }
 });
 
-ring_func!(ring_slint_timer_stop, |p| {
+ring_func!(ring_slint_model_view_clear_filter, |p| {
     ring_check_paracount!(p, 1);
     ring_check_number!(p, 1);
 
-    let timer_id = ring_get_int!(p, 1) as u32;
-    if let Err(e) = slint::timer_stop(timer_id) {
+    let view_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::model_view_clear_filter(view_id) {
         ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_timer_running, |p| {
+ring_func!(ring_slint_model_view_count, |p| {
     ring_check_paracount!(p, 1);
     ring_check_number!(p, 1);
 
-    let timer_id = ring_get_int!(p, 1) as u32;
-    match slint::timer_running(timer_id) {
-        Ok(running) => ring_ret_number!(p, if running { 1.0 } else { 0.0 }),
+    let view_id = ring_get_int!(p, 1) as u32;
+    match slint::model_view_count(view_id) {
+        Ok(count) => ring_ret_number!(p, count as f64),
         Err(e) => ring_error!(p, &e),
     }
 });
 
-ring_func!(ring_slint_timer_restart, |p| {
-    ring_check_paracount!(p, 1);
+ring_func!(ring_slint_model_view_source_index, |p| {
+    ring_check_paracount!(p, 2);
     ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
 
-    let timer_id = ring_get_int!(p, 1) as u32;
-    if let Err(e) = slint::timer_restart(timer_id) {
-        ring_error!(p, &e);
+    let view_id = ring_get_int!(p, 1) as u32;
+    let row = ring_get_int!(p, 2) as usize;
+    match slint::model_view_source_index(view_id, row) {
+        Ok(index) => ring_ret_number!(p, index as f64),
+        Err(e) => ring_error!(p, &e),
     }
 });
 
-ring_func!(ring_slint_timer_set_interval, |p| {
-    ring_check_paracount!(p, 2);
+ring_func!(ring_slint_model_view_destroy, |p| {
+    ring_check_paracount!(p, 1);
     ring_check_number!(p, 1);
-    ring_check_number!(p, 2);
 
-    let timer_id = ring_get_int!(p, 1) as u32;
-    let interval_ms = ring_get_int!(p, 2) as u64;
-    if let Err(e) = slint::timer_set_interval(timer_id, interval_ms) {
+    let view_id = ring_get_int!(p, 1) as u32;
+    if let Err(e) = slint::model_view_destroy(view_id) {
         ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_model_create, |p| {
-    ring_check_paracount!(p, 2);
+ring_func!(ring_slint_lazy_model_create, |p| {
+    ring_check_paracount!(p, 4);
     ring_check_cpointer!(p, 1);
     ring_check_string!(p, 2);
+    ring_check_string!(p, 3);
+    ring_check_string!(p, 4);
 
     if let Some(wrapper) = ring_get_pointer!(
         p,
@@ -701,8 +2024,19 @@ ring_func!(ring_slint_model_create, |p| {
         slint::SLINT_INSTANCE_TYPE
     ) {
         let prop_name = ring_get_string!(p, 2);
-        match slint::model_create(&wrapper.instance, prop_name) {
-            Ok(model_id) => ring_ret_number!(p, model_id as f64),
+        let count_handler = ring_get_string!(p, 3);
+        let row_handler = ring_get_string!(p, 4);
+        match slint::lazy_model_create(
+            &wrapper.instance,
+            prop_name,
+            p as RingVM,
+            count_handler,
+            row_handler,
+        ) {
+            Ok(model_id) => {
+                wrapper.record_property(prop_name);
+                ring_ret_number!(p, model_id as f64);
+            }
             Err(e) => ring_error!(p, &e),
         }
     } else {
@@ -710,82 +2044,76 @@ ring_func!(ring_slint_model_create, |p| {
     }
 });
 
-ring_func!(ring_slint_model_push, |p| {
+ring_func!(ring_slint_lazy_model_set_cache_size, |p| {
     ring_check_paracount!(p, 2);
     ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
 
     let model_id = ring_get_int!(p, 1) as u32;
-    let value = slint::ring_param_to_model_value(p, 2);
-    if let Err(e) = slint::model_push(model_id, value) {
+    let rows = ring_get_int!(p, 2).max(0) as usize;
+    if let Err(e) = slint::lazy_model_set_cache_size(model_id, rows) {
         ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_model_remove, |p| {
-    ring_check_paracount!(p, 2);
+ring_func!(ring_slint_lazy_model_rows_changed, |p| {
+    ring_check_paracount!(p, 3);
     ring_check_number!(p, 1);
     ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
 
     let model_id = ring_get_int!(p, 1) as u32;
     let index = ring_get_int!(p, 2) as usize;
-    if let Err(e) = slint::model_remove(model_id, index) {
+    let count = ring_get_int!(p, 3) as usize;
+    if let Err(e) = slint::lazy_model_rows_changed(model_id, index, count) {
         ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_model_set, |p| {
+ring_func!(ring_slint_lazy_model_rows_added, |p| {
     ring_check_paracount!(p, 3);
     ring_check_number!(p, 1);
     ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
 
     let model_id = ring_get_int!(p, 1) as u32;
     let index = ring_get_int!(p, 2) as usize;
-    let value = slint::ring_param_to_model_value(p, 3);
-    if let Err(e) = slint::model_set(model_id, index, value) {
+    let count = ring_get_int!(p, 3) as usize;
+    if let Err(e) = slint::lazy_model_rows_added(model_id, index, count) {
         ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_model_count, |p| {
-    ring_check_paracount!(p, 1);
-    ring_check_number!(p, 1);
-
-    let model_id = ring_get_int!(p, 1) as u32;
-    match slint::model_count(model_id) {
-        Ok(count) => ring_ret_number!(p, count as f64),
-        Err(e) => ring_error!(p, &e),
-    }
-});
-
-ring_func!(ring_slint_model_clear, |p| {
-    ring_check_paracount!(p, 1);
+ring_func!(ring_slint_lazy_model_rows_removed, |p| {
+    ring_check_paracount!(p, 3);
     ring_check_number!(p, 1);
+    ring_check_number!(p, 2);
+    ring_check_number!(p, 3);
 
     let model_id = ring_get_int!(p, 1) as u32;
-    if let Err(e) = slint::model_clear(model_id) {
+    let index = ring_get_int!(p, 2) as usize;
+    let count = ring_get_int!(p, 3) as usize;
+    if let Err(e) = slint::lazy_model_rows_removed(model_id, index, count) {
         ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_model_insert, |p| {
-    ring_check_paracount!(p, 3);
+ring_func!(ring_slint_lazy_model_reset, |p| {
+    ring_check_paracount!(p, 1);
     ring_check_number!(p, 1);
-    ring_check_number!(p, 2);
 
     let model_id = ring_get_int!(p, 1) as u32;
-    let index = ring_get_int!(p, 2) as usize;
-    let value = slint::ring_param_to_model_value(p, 3);
-    if let Err(e) = slint::model_insert(model_id, index, value) {
+    if let Err(e) = slint::lazy_model_reset(model_id) {
         ring_error!(p, &e);
     }
 });
 
-ring_func!(ring_slint_model_destroy, |p| {
+ring_func!(ring_slint_lazy_model_destroy, |p| {
     ring_check_paracount!(p, 1);
     ring_check_number!(p, 1);
 
     let model_id = ring_get_int!(p, 1) as u32;
-    if let Err(e) = slint::model_destroy(model_id) {
+    if let Err(e) = slint::lazy_model_destroy(model_id) {
         ring_error!(p, &e);
     }
 });
@@ -1114,6 +2442,21 @@ ring_func!(ring_slint_definition_globals, |p| {
     }
 });
 
+ring_func!(ring_slint_definition_describe, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_cpointer!(p, 1);
+
+    if let Some(comp) =
+        ring_get_pointer!(p, 1, slint::SlintComponentDef, slint::SLINT_COMPONENT_TYPE)
+    {
+        let list = ring_api_newlist(p);
+        slint::add_definition_description(list, &comp.definition);
+        ring_ret_list!(p, list);
+    } else {
+        ring_error!(p, "Invalid SlintComponent pointer");
+    }
+});
+
 ring_func!(ring_slint_model_get, |p| {
     ring_check_paracount!(p, 2);
     ring_check_number!(p, 1);
@@ -1127,6 +2470,17 @@ ring_func!(ring_slint_model_get, |p| {
     }
 });
 
+ring_func!(ring_slint_model_get_json, |p| {
+    ring_check_paracount!(p, 1);
+    ring_check_number!(p, 1);
+
+    let model_id = ring_get_int!(p, 1) as u32;
+    match slint::model_value(model_id) {
+        Ok(value) => ring_ret_string!(p, &slint::value_to_json_string(&value)),
+        Err(e) => ring_error!(p, &e),
+    }
+});
+
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 ring_func!(ring_slint_window_drag, |p| {
     ring_check_paracount!(p, 1);
@@ -1547,6 +2901,83 @@ ring_func!(ring_slint_window_set_icon, |p| {
     }
 });
 
+fn add_diagnostic_fields(record: RingList, diag: &slint::CompileDiagnostic) {
+    let file = ring_list_newlist(record);
+    ring_list_addstring_str(file, "file");
+    ring_list_addstring_str(file, &diag.file);
+
+    let line = ring_list_newlist(record);
+    ring_list_addstring_str(line, "line");
+    ring_list_addint(line, diag.line as i32);
+
+    let column = ring_list_newlist(record);
+    ring_list_addstring_str(column, "column");
+    ring_list_addint(column, diag.column as i32);
+
+    let severity = ring_list_newlist(record);
+    ring_list_addstring_str(severity, "severity");
+    ring_list_addstring_str(severity, diag.severity);
+
+    let message = ring_list_newlist(record);
+    ring_list_addstring_str(message, "message");
+    ring_list_addstring_str(message, &diag.message);
+}
+
+fn ring_ret_slint_value(p: *mut libc::c_void, value: Value) {
+    let ptr = Box::into_raw(Box::new(slint::SlintValueWrapper { value }));
+    ring_ret_managed_cpointer!(p, ptr, slint::SLINT_VALUE_TYPE, free_slint_value);
+}
+
+/// Returns a number, or a string with a unit such as "2s", as a SlintValue.
+fn ring_ret_unit_value(p: *mut libc::c_void, kind: &str, parse: fn(&str) -> Option<f64>) {
+    ring_check_paracount!(p, 1);
+
+    if ring_api_isnumber(p, 1) {
+        ring_ret_slint_value(p, Value::Number(ring_api_getnumber(p, 1)));
+    } else if ring_api_isstring(p, 1) {
+        let text = ring_get_string!(p, 1);
+        match parse(text) {
+            Some(n) => ring_ret_slint_value(p, Value::Number(n)),
+            None => ring_error!(p, &format!("Invalid {} '{}'", kind, text)),
+        }
+    } else {
+        ring_error!(p, &format!("Expected a number or a {} string", kind));
+    }
+}
+
+/// The handler named by parameter `param`, passed only as many arguments as
+/// the optional number after it says. Raises the Ring error itself on None.
+fn ring_get_handler(
+    p: *mut libc::c_void,
+    param: i32,
+    paracount: i32,
+) -> Option<slint::RingHandler> {
+    let ring_func = ring_get_string!(p, param);
+    if paracount <= param {
+        return Some(slint::RingHandler::new(ring_func));
+    }
+    if !ring_api_isnumber(p, param + 1) {
+        ring_error!(p, "The parameter count must be a number");
+        return None;
+    }
+    let params = ring_get_int!(p, param + 1);
+    if params < 0 {
+        ring_error!(p, "The parameter count cannot be negative");
+        return None;
+    }
+    Some(slint::RingHandler::with_params(ring_func, params as usize))
+}
+
+/// Raw bytes of a Ring string parameter, which may hold binary data.
+fn ring_get_bytes<'a>(p: *mut libc::c_void, param: i32) -> &'a [u8] {
+    let ptr = ring_api_getstring(p, param);
+    let size = ring_api_getstringsize(p, param);
+    if ptr.is_null() || size <= 0 {
+        return &[];
+    }
+    unsafe { std::slice::from_raw_parts(ptr as *const u8, size as usize) }
+}
+
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 fn parse_file_filters(p: *mut libc::c_void, param: usize) -> Vec<(String, Vec<String>)> {
     let mut filters = Vec::new();
@@ -1578,8 +3009,28 @@ ring_libinit! {
     "slint_add_library_path" => ring_slint_add_library_path,
     "slint_remove_library_path" => ring_slint_remove_library_path,
     "slint_clear_library_paths" => ring_slint_clear_library_paths,
+    "slint_add_include_path" => ring_slint_add_include_path,
+    "slint_remove_include_path" => ring_slint_remove_include_path,
+    "slint_clear_include_paths" => ring_slint_clear_include_paths,
+    "slint_add_virtual_file" => ring_slint_add_virtual_file,
+    "slint_remove_virtual_file" => ring_slint_remove_virtual_file,
+    "slint_clear_virtual_files" => ring_slint_clear_virtual_files,
     "slint_load" => ring_slint_load,
     "slint_loadstring" => ring_slint_loadstring,
+    "slint_load_component" => ring_slint_load_component,
+    "slint_loadstring_component" => ring_slint_loadstring_component,
+    "slint_component_names" => ring_slint_component_names,
+    "slint_component_get" => ring_slint_component_get,
+    "slint_load_diagnostics" => ring_slint_load_diagnostics,
+    "slint_compile" => ring_slint_compile,
+    "slint_compile_string" => ring_slint_compile_string,
+    "slint_result_has_errors" => ring_slint_result_has_errors,
+    "slint_result_diagnostics" => ring_slint_result_diagnostics,
+    "slint_result_component_names" => ring_slint_result_component_names,
+    "slint_result_component" => ring_slint_result_component,
+    "slint_result_types" => ring_slint_result_types,
+    "slint_result_enums" => ring_slint_result_enums,
+    "slint_result_structs" => ring_slint_result_structs,
     "slint_create" => ring_slint_create,
     "slint_show" => ring_slint_show,
     "slint_hide" => ring_slint_hide,
@@ -1587,33 +3038,84 @@ ring_libinit! {
     "slint_quit" => ring_slint_quit,
     "slint_get" => ring_slint_get,
     "slint_set" => ring_slint_set,
+    "slint_get_json" => ring_slint_get_json,
+    "slint_set_json" => ring_slint_set_json,
     "slint_set_bool" => ring_slint_set_bool,
     "slint_set_image" => ring_slint_set_image,
+    "slint_image_from_bytes" => ring_slint_image_from_bytes,
+    "slint_image_from_rgba" => ring_slint_image_from_rgba,
+    "slint_image_from_rgb" => ring_slint_image_from_rgb,
+    "slint_image_info" => ring_slint_image_info,
+    "slint_image_data" => ring_slint_image_data,
+    "slint_value_bool" => ring_slint_value_bool,
+    "slint_value_enum" => ring_slint_value_enum,
+    "slint_value_color" => ring_slint_value_color,
+    "slint_value_image" => ring_slint_value_image,
+    "slint_value_length" => ring_slint_value_length,
+    "slint_value_duration" => ring_slint_value_duration,
+    "slint_value_angle" => ring_slint_value_angle,
     "slint_set_string" => ring_slint_set_string,
     "slint_set_number" => ring_slint_set_number,
     "slint_set_color" => ring_slint_set_color,
+    "slint_color_format" => ring_slint_color_format,
     "slint_set_enum" => ring_slint_set_enum,
     "slint_on" => ring_slint_on,
     "slint_invoke" => ring_slint_invoke,
     "slint_callback_arg" => ring_slint_callback_arg,
     "slint_callback_args_count" => ring_slint_callback_args_count,
+    "slint_callback_return" => ring_slint_callback_return,
     "slint_global_get" => ring_slint_global_get,
     "slint_global_set" => ring_slint_global_set,
+    "slint_global_get_json" => ring_slint_global_get_json,
+    "slint_global_set_json" => ring_slint_global_set_json,
     "slint_global_on" => ring_slint_global_on,
     "slint_global_invoke" => ring_slint_global_invoke,
+    "slint_hot_reload" => ring_slint_hot_reload,
+    "slint_hot_reload_stop" => ring_slint_hot_reload_stop,
+    "slint_hot_reload_diagnostics" => ring_slint_hot_reload_diagnostics,
+    "slint_watch" => ring_slint_watch,
+    "slint_global_watch" => ring_slint_global_watch,
+    "slint_unwatch" => ring_slint_unwatch,
     "slint_timer_start" => ring_slint_timer_start,
     "slint_timer_stop" => ring_slint_timer_stop,
     "slint_timer_running" => ring_slint_timer_running,
     "slint_timer_restart" => ring_slint_timer_restart,
     "slint_timer_set_interval" => ring_slint_timer_set_interval,
     "slint_model_create" => ring_slint_model_create,
+    "slint_model_adopt" => ring_slint_model_adopt,
+    "slint_model_rows" => ring_slint_model_rows,
+    "slint_model_create_global" => ring_slint_model_create_global,
+    "slint_model_create_nested" => ring_slint_model_create_nested,
     "slint_model_push" => ring_slint_model_push,
     "slint_model_remove" => ring_slint_model_remove,
     "slint_model_set" => ring_slint_model_set,
     "slint_model_count" => ring_slint_model_count,
     "slint_model_clear" => ring_slint_model_clear,
     "slint_model_insert" => ring_slint_model_insert,
+    "slint_model_push_many" => ring_slint_model_push_many,
+    "slint_model_replace" => ring_slint_model_replace,
+    "slint_model_remove_range" => ring_slint_model_remove_range,
+    "slint_model_swap" => ring_slint_model_swap,
+    "slint_model_move" => ring_slint_model_move,
     "slint_model_destroy" => ring_slint_model_destroy,
+    "slint_model_on_change" => ring_slint_model_on_change,
+    "slint_model_view_create" => ring_slint_model_view_create,
+    "slint_model_view_sort_by" => ring_slint_model_view_sort_by,
+    "slint_model_view_sort_func" => ring_slint_model_view_sort_func,
+    "slint_model_view_filter" => ring_slint_model_view_filter,
+    "slint_model_view_filter_func" => ring_slint_model_view_filter_func,
+    "slint_model_view_search" => ring_slint_model_view_search,
+    "slint_model_view_clear_filter" => ring_slint_model_view_clear_filter,
+    "slint_model_view_count" => ring_slint_model_view_count,
+    "slint_model_view_source_index" => ring_slint_model_view_source_index,
+    "slint_model_view_destroy" => ring_slint_model_view_destroy,
+    "slint_lazy_model_create" => ring_slint_lazy_model_create,
+    "slint_lazy_model_set_cache_size" => ring_slint_lazy_model_set_cache_size,
+    "slint_lazy_model_rows_changed" => ring_slint_lazy_model_rows_changed,
+    "slint_lazy_model_rows_added" => ring_slint_lazy_model_rows_added,
+    "slint_lazy_model_rows_removed" => ring_slint_lazy_model_rows_removed,
+    "slint_lazy_model_reset" => ring_slint_lazy_model_reset,
+    "slint_lazy_model_destroy" => ring_slint_lazy_model_destroy,
     "slint_window_set_minimized" => ring_slint_window_set_minimized,
     "slint_window_is_minimized" => ring_slint_window_is_minimized,
     "slint_window_set_maximized" => ring_slint_window_set_maximized,
@@ -1632,7 +3134,9 @@ ring_libinit! {
     "slint_definition_callbacks" => ring_slint_definition_callbacks,
     "slint_definition_functions" => ring_slint_definition_functions,
     "slint_definition_globals" => ring_slint_definition_globals,
+    "slint_definition_describe" => ring_slint_definition_describe,
     "slint_model_get" => ring_slint_model_get,
+    "slint_model_get_json" => ring_slint_model_get_json,
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     {
         "slint_window_drag" => ring_slint_window_drag,
@@ -1,29 +1,92 @@
-use crate::slint::CALLBACK_ARGS;
+use crate::slint::{
+    CALLBACK_FRAMES, CallbackFrame, RingHandler, callback_arg_types, callback_return_type,
+    default_value_for_type,
+};
+use i_slint_compiler::langtype::Type as LangType;
 use ring_lang_rs::{RingVM, ring_vm_runcode_str};
 use slint_interpreter::{ComponentInstance, Value};
 
+/// Ring code calling `handler` with the first `param_count` arguments of the
+/// running frame as parameters.
+fn handler_call(handler: &RingHandler, param_count: usize, return_type: &LangType) -> String {
+    let params: Vec<String> = (1..=param_count)
+        .map(|i| format!("slint_callback_arg({})", i))
+        .collect();
+    let call = handler.call_code(&params.join(", "));
+    if matches!(return_type, LangType::Void) {
+        call
+    } else {
+        format!("slint_callback_return({})", call)
+    }
+}
+
+/// Runs a Ring handler with `args` as its parameters and converts what it
+/// returns to `return_type`. A handler registered with a parameter count gets
+/// only that many of the arguments.
+pub fn run_ring_handler(
+    vm_ptr: RingVM,
+    handler: &RingHandler,
+    args: &[Value],
+    return_type: &LangType,
+) -> Value {
+    CALLBACK_FRAMES.with(|cell| {
+        cell.borrow_mut().push(CallbackFrame {
+            args: args.to_vec(),
+            return_type: return_type.clone(),
+            returned: None,
+        })
+    });
+
+    let param_count = handler
+        .params()
+        .map_or(args.len(), |params| params.min(args.len()));
+    ring_vm_runcode_str(vm_ptr, &handler_call(handler, param_count, return_type));
+
+    let returned = CALLBACK_FRAMES
+        .with(|cell| cell.borrow_mut().pop())
+        .and_then(|frame| frame.returned);
+
+    if matches!(return_type, LangType::Void) {
+        return Value::Void;
+    }
+    // slint_callback_return() already converted the value to `return_type`.
+    returned.unwrap_or_else(|| default_value_for_type(return_type))
+}
+
+/// Checks that a handler registered with a parameter count does not ask for
+/// more parameters than the callback has arguments.
+fn check_params(
+    handler: &RingHandler,
+    arg_types: Option<Vec<LangType>>,
+    callback_name: &str,
+) -> Result<(), String> {
+    match (handler.params(), arg_types) {
+        (Some(params), Some(arg_types)) if params > arg_types.len() => Err(format!(
+            "Callback '{}' has {} argument(s), cannot pass {} parameter(s)",
+            callback_name,
+            arg_types.len(),
+            params
+        )),
+        _ => Ok(()),
+    }
+}
+
 pub fn set_callback(
     instance: &ComponentInstance,
     callback_name: &str,
     vm_ptr: RingVM,
-    ring_func_name: String,
+    handler: RingHandler,
 ) -> Result<(), String> {
+    let definition = instance.definition();
+    check_params(
+        &handler,
+        callback_arg_types(&definition, None, callback_name),
+        callback_name,
+    )?;
+    let return_type = callback_return_type(&definition, None, callback_name);
     instance
         .set_callback(callback_name, move |args: &[Value]| -> Value {
-            CALLBACK_ARGS.with(|cell| {
-                *cell.borrow_mut() = args.to_vec();
-            });
-
-            let func_without_parens = if ring_func_name.ends_with("()") {
-                ring_func_name.trim_end_matches("()")
-            } else {
-                &ring_func_name
-            };
-
-            let code = format!("{}()", func_without_parens);
-            ring_vm_runcode_str(vm_ptr, &code);
-
-            Value::Void
+            run_ring_handler(vm_ptr, &handler, args, &return_type)
         })
         .map_err(|e| format!("Set callback error: {:?}", e))
 }
@@ -33,35 +96,49 @@ pub fn set_global_callback(
     global: &str,
     callback_name: &str,
     vm_ptr: RingVM,
-    ring_func_name: String,
+    handler: RingHandler,
 ) -> Result<(), String> {
+    let definition = instance.definition();
+    check_params(
+        &handler,
+        callback_arg_types(&definition, Some(global), callback_name),
+        callback_name,
+    )?;
+    let return_type = callback_return_type(&definition, Some(global), callback_name);
     instance
         .set_global_callback(global, callback_name, move |args: &[Value]| -> Value {
-            CALLBACK_ARGS.with(|cell| {
-                *cell.borrow_mut() = args.to_vec();
-            });
-
-            let func_without_parens = if ring_func_name.ends_with("()") {
-                ring_func_name.trim_end_matches("()")
-            } else {
-                &ring_func_name
-            };
-
-            let code = format!("{}()", func_without_parens);
-            ring_vm_runcode_str(vm_ptr, &code);
-
-            Value::Void
+            run_ring_handler(vm_ptr, &handler, args, &return_type)
         })
         .map_err(|e| format!("Set global callback error: {:?}", e))
 }
 
+/// Declared return type of the running handler's callback, or None when no
+/// handler is running.
+pub fn running_callback_return_type() -> Option<LangType> {
+    CALLBACK_FRAMES.with(|cell| cell.borrow().last().map(|frame| frame.return_type.clone()))
+}
+
+/// Stores the value returned by the running handler. Returns false when no
+/// handler is running.
+pub fn set_callback_return(value: Value) -> bool {
+    CALLBACK_FRAMES.with(|cell| match cell.borrow_mut().last_mut() {
+        Some(frame) => {
+            frame.returned = Some(value);
+            true
+        }
+        None => false,
+    })
+}
+
 pub fn get_callback_arg(index: usize) -> Option<Value> {
-    CALLBACK_ARGS.with(|cell| {
-        let args = cell.borrow();
-        args.get(index).cloned()
+    CALLBACK_FRAMES.with(|cell| {
+        let frames = cell.borrow();
+        frames
+            .last()
+            .and_then(|frame| frame.args.get(index).cloned())
     })
 }
 
 pub fn get_callback_args_count() -> usize {
-    CALLBACK_ARGS.with(|cell| cell.borrow().len())
+    CALLBACK_FRAMES.with(|cell| cell.borrow().last().map_or(0, |frame| frame.args.len()))
 }
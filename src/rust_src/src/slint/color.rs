@@ -0,0 +1,396 @@
+use i_slint_core::graphics::Color;
+use std::cell::RefCell;
+
+thread_local! {
+    static COLORS_AS_LISTS: RefCell<bool> = const { RefCell::new(false) };
+}
+
+/// CSS named colors, as 0xRRGGBB.
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+/// Parses `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()`,
+/// `hsl()`, `hsla()` and CSS color names.
+pub fn parse_color(s: &str) -> Option<Color> {
+    parse_color_literal(s).or_else(|| parse_named_color(s.trim()))
+}
+
+/// Like `parse_color()` but without color names, for guessing the type of
+/// untyped strings, where "red" is more likely text than a color.
+pub fn parse_color_literal(s: &str) -> Option<Color> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    let open = s.find('(')?;
+    let args = s[open + 1..].strip_suffix(')')?;
+    match s[..open].trim().to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => parse_rgb_args(args),
+        "hsl" | "hsla" => parse_hsl_args(args),
+        _ => None,
+    }
+}
+
+fn parse_named_color(s: &str) -> Option<Color> {
+    if s.eq_ignore_ascii_case("transparent") {
+        return Some(Color::from_argb_u8(0, 0, 0, 0));
+    }
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        .map(|(_, rgb)| Color::from_argb_encoded(0xff000000 | rgb))
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 => Some(Color::from_argb_u8(255, digit(0)?, digit(1)?, digit(2)?)),
+        4 => Some(Color::from_argb_u8(
+            digit(3)?,
+            digit(0)?,
+            digit(1)?,
+            digit(2)?,
+        )),
+        6 => Some(Color::from_argb_u8(255, byte(0)?, byte(2)?, byte(4)?)),
+        8 => Some(Color::from_argb_u8(byte(6)?, byte(0)?, byte(2)?, byte(4)?)),
+        _ => None,
+    }
+}
+
+/// Splits `1, 2, 3`, `1 2 3` and `1 2 3 / 0.5` into the color channels and
+/// the optional alpha.
+fn split_color_args(args: &str) -> Option<(Vec<&str>, Option<&str>)> {
+    let (channels, alpha) = match args.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (args, None),
+    };
+    let mut parts: Vec<&str> = channels
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    match (parts.len(), alpha) {
+        (3, _) => Some((parts, alpha)),
+        (4, None) => {
+            let alpha = parts.pop();
+            Some((parts, alpha))
+        }
+        _ => None,
+    }
+}
+
+/// A number, or a percentage of `scale`.
+fn parse_component(s: &str, scale: f32) -> Option<f32> {
+    match s.strip_suffix('%') {
+        Some(percent) => percent
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .map(|p| p * scale / 100.0),
+        None => s.parse().ok(),
+    }
+}
+
+/// Alpha as 0-255, from a 0-1 number or a percentage.
+fn parse_alpha(alpha: Option<&str>) -> Option<u8> {
+    match alpha {
+        Some(alpha) => Some(to_channel(parse_component(alpha, 1.0)? * 255.0)),
+        None => Some(255),
+    }
+}
+
+fn to_channel(v: f32) -> u8 {
+    v.round().clamp(0.0, 255.0) as u8
+}
+
+fn parse_rgb_args(args: &str) -> Option<Color> {
+    let (channels, alpha) = split_color_args(args)?;
+    let red = to_channel(parse_component(channels[0], 255.0)?);
+    let green = to_channel(parse_component(channels[1], 255.0)?);
+    let blue = to_channel(parse_component(channels[2], 255.0)?);
+    Some(Color::from_argb_u8(parse_alpha(alpha)?, red, green, blue))
+}
+
+fn parse_hsl_args(args: &str) -> Option<Color> {
+    let (channels, alpha) = split_color_args(args)?;
+    let hue: f32 = channels[0].trim_end_matches("deg").parse().ok()?;
+    let saturation = (parse_component(channels[1], 1.0)?).clamp(0.0, 1.0);
+    let lightness = (parse_component(channels[2], 1.0)?).clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    Some(Color::from_argb_u8(
+        parse_alpha(alpha)?,
+        to_channel((r + m) * 255.0),
+        to_channel((g + m) * 255.0),
+        to_channel((b + m) * 255.0),
+    ))
+}
+
+/// A color from `[r, g, b]` or `[r, g, b, a]` channels, each 0-255.
+pub fn color_from_channels(channels: &[f64]) -> Option<Color> {
+    let channel = |i: usize| {
+        let v = *channels.get(i)?;
+        (0.0..=255.0).contains(&v).then_some(v.round() as u8)
+    };
+    match channels.len() {
+        3 => Some(Color::from_argb_u8(
+            255,
+            channel(0)?,
+            channel(1)?,
+            channel(2)?,
+        )),
+        4 => Some(Color::from_argb_u8(
+            channel(3)?,
+            channel(0)?,
+            channel(1)?,
+            channel(2)?,
+        )),
+        _ => None,
+    }
+}
+
+pub fn color_to_hex(c: Color) -> String {
+    if c.alpha() == 255 {
+        format!("#{:02x}{:02x}{:02x}", c.red(), c.green(), c.blue())
+    } else {
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            c.red(),
+            c.green(),
+            c.blue(),
+            c.alpha()
+        )
+    }
+}
+
+/// Whether colors read back to Ring are `[r, g, b, a]` lists instead of hex
+/// strings.
+pub fn colors_as_lists() -> bool {
+    COLORS_AS_LISTS.with(|flag| *flag.borrow())
+}
+
+pub fn set_colors_as_lists(as_lists: bool) {
+    COLORS_AS_LISTS.with(|flag| *flag.borrow_mut() = as_lists);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Option<String> {
+        parse_color(s).map(color_to_hex)
+    }
+
+    #[test]
+    fn parses_hex_forms() {
+        assert_eq!(hex("#abc").as_deref(), Some("#aabbcc"));
+        assert_eq!(hex("#abc8").as_deref(), Some("#aabbcc88"));
+        assert_eq!(hex("#FF0000").as_deref(), Some("#ff0000"));
+        assert_eq!(hex("#00000080").as_deref(), Some("#00000080"));
+        assert_eq!(hex(" #f80 ").as_deref(), Some("#ff8800"));
+        assert_eq!(hex("#abcd1"), None);
+        assert_eq!(hex("#ggg"), None);
+    }
+
+    #[test]
+    fn parses_rgb_and_rgba() {
+        assert_eq!(hex("rgb(255, 128, 0)").as_deref(), Some("#ff8000"));
+        assert_eq!(hex("rgb(255 128 0)").as_deref(), Some("#ff8000"));
+        assert_eq!(hex("RGB(100%, 0%, 0%)").as_deref(), Some("#ff0000"));
+        assert_eq!(hex("rgba(0, 0, 0, 0.25)").as_deref(), Some("#00000040"));
+        assert_eq!(hex("rgb(0 0 255 / 50%)").as_deref(), Some("#0000ff80"));
+        assert_eq!(hex("rgb(1, 2)"), None);
+        assert_eq!(hex("rgb(1, 2, 3"), None);
+    }
+
+    #[test]
+    fn parses_hsl_and_hsla() {
+        assert_eq!(hex("hsl(0, 100%, 50%)").as_deref(), Some("#ff0000"));
+        assert_eq!(hex("hsl(210deg, 80%, 60%)").as_deref(), Some("#4799eb"));
+        assert_eq!(
+            hex("hsla(240, 100%, 50%, 0.5)").as_deref(),
+            Some("#0000ff80")
+        );
+        assert_eq!(hex("hsl(0, 0%, 100%)").as_deref(), Some("#ffffff"));
+        assert_eq!(hex("hsl(red, 0%, 0%)"), None);
+    }
+
+    #[test]
+    fn parses_names() {
+        assert_eq!(hex("red").as_deref(), Some("#ff0000"));
+        assert_eq!(hex("SteelBlue").as_deref(), Some("#4682b4"));
+        assert_eq!(hex("transparent").as_deref(), Some("#00000000"));
+        assert_eq!(hex("notacolor"), None);
+    }
+
+    #[test]
+    fn literals_skip_names() {
+        assert!(parse_color_literal("red").is_none());
+        assert_eq!(
+            parse_color_literal("#f00").map(color_to_hex).as_deref(),
+            Some("#ff0000")
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use ring_lang_rs::{RingVM, ring_vm_runcode_str};
+
+/// Start of the handler slintMethod() returns: an index into the global list
+/// slint.ring uses to keep bound objects alive (Ring names are case-insensitive).
+const BOUND_OBJECTS_PREFIX: &str = "$aslintboundobjects[";
+
+/// Ring code called back by Slint, timers, hotkeys and the tray: a function
+/// name (anonymous functions evaluate to one too) or a method bound to an
+/// object with slintMethod(), e.g. `$aSlintBoundObjects[2].onClick`.
+#[derive(Clone)]
+pub struct RingHandler {
+    target: String,
+    /// How many of the arguments are passed; all of them when None.
+    params: Option<usize>,
+}
+
+impl RingHandler {
+    pub fn new(target: &str) -> Self {
+        Self {
+            target: target.trim().trim_end_matches("()").to_string(),
+            params: None,
+        }
+    }
+
+    /// A handler that is passed only the first `params` arguments, for Ring
+    /// functions declaring fewer parameters than the callback has arguments.
+    pub fn with_params(target: &str, params: usize) -> Self {
+        Self {
+            params: Some(params),
+            ..Self::new(target)
+        }
+    }
+
+    pub fn params(&self) -> Option<usize> {
+        self.params
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.target
+    }
+
+    /// Ring expression calling the handler with `params` (already Ring code).
+    pub fn call_code(&self, params: &str) -> String {
+        format!("{}({})", self.target, params)
+    }
+
+    pub fn call(&self, vm: RingVM) {
+        ring_vm_runcode_str(vm, &self.call_code(""));
+    }
+
+    fn key(&self) -> String {
+        self.target.to_lowercase()
+    }
+
+    fn bound_slot(&self) -> Option<u32> {
+        let target = self.target.to_lowercase();
+        let rest = target.strip_prefix(BOUND_OBJECTS_PREFIX)?;
+        rest[..rest.find(']')?].trim().parse().ok()
+    }
+
+    /// Releases each distinct handler once, e.g. all the registrations of a
+    /// freed instance.
+    pub fn release_all(vm: RingVM, handlers: impl IntoIterator<Item = RingHandler>) {
+        let mut released: Vec<String> = Vec::new();
+        for handler in handlers {
+            if !released.contains(&handler.key()) {
+                released.push(handler.key());
+                handler.release(vm);
+            }
+        }
+    }
+
+    /// Lets go of the object kept alive for a bound method. Plain function
+    /// names need no cleanup.
+    pub fn release(&self, vm: RingVM) {
+        if let Some(slot) = self.bound_slot() {
+            ring_vm_runcode_str(vm, &format!("slintReleaseMethod({})", slot));
+        }
+    }
+}
@@ -1,15 +1,16 @@
+use crate::slint::RingHandler;
 use global_hotkey::{
     GlobalHotKeyEvent, GlobalHotKeyManager,
     hotkey::{Code, HotKey, Modifiers},
 };
-use ring_lang_rs::{RingVM, ring_vm_runcode_str};
+use ring_lang_rs::RingVM;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::str::FromStr;
 
 thread_local! {
     static HOTKEY_MANAGER: RefCell<Option<GlobalHotKeyManager>> = const { RefCell::new(None) };
-    static HOTKEY_CALLBACKS: RefCell<HashMap<u32, (RingVM, String)>> = RefCell::new(HashMap::new());
+    static HOTKEY_CALLBACKS: RefCell<HashMap<u32, (RingVM, RingHandler)>> = RefCell::new(HashMap::new());
     static REGISTERED_HOTKEYS: RefCell<HashMap<u32, HotKey>> = RefCell::new(HashMap::new());
 }
 
@@ -77,7 +78,7 @@ pub fn hotkey_register(
     });
 
     HOTKEY_CALLBACKS.with(|c| {
-        c.borrow_mut().insert(id, (vm, callback));
+        c.borrow_mut().insert(id, (vm, RingHandler::new(&callback)));
     });
 
     Ok(id)
@@ -98,9 +99,10 @@ pub fn hotkey_unregister(id: u32) -> Result<(), String> {
             }
         })?;
 
-        HOTKEY_CALLBACKS.with(|c| {
-            c.borrow_mut().remove(&id);
-        });
+        let callback = HOTKEY_CALLBACKS.with(|c| c.borrow_mut().remove(&id));
+        if let Some((vm, handler)) = callback {
+            handler.release(vm);
+        }
     }
 
     Ok(())
@@ -117,9 +119,10 @@ pub fn hotkey_unregister_all() -> Result<(), String> {
                 let _ = manager.unregister(hk);
             }
         });
-        HOTKEY_CALLBACKS.with(|c| {
-            c.borrow_mut().remove(&id);
-        });
+        let callback = HOTKEY_CALLBACKS.with(|c| c.borrow_mut().remove(&id));
+        if let Some((vm, handler)) = callback {
+            handler.release(vm);
+        }
         REGISTERED_HOTKEYS.with(|h| {
             h.borrow_mut().remove(&id);
         });
@@ -130,11 +133,9 @@ pub fn hotkey_unregister_all() -> Result<(), String> {
 
 pub fn hotkey_poll() {
     if let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
-        HOTKEY_CALLBACKS.with(|c| {
-            if let Some((vm, callback)) = c.borrow().get(&event.id) {
-                let code = format!("{}()", callback);
-                ring_vm_runcode_str(*vm, &code);
-            }
-        });
+        let callback = HOTKEY_CALLBACKS.with(|c| c.borrow().get(&event.id).cloned());
+        if let Some((vm, handler)) = callback {
+            handler.call(vm);
+        }
     }
 }
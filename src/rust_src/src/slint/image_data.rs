@@ -0,0 +1,118 @@
+use crate::slint::instance_get_property;
+use i_slint_core::graphics::{Rgb8Pixel, Rgba8Pixel, SharedPixelBuffer};
+use slint_interpreter::{ComponentInstance, Image, Value};
+
+fn looks_like_svg(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(1024)];
+    let head = String::from_utf8_lossy(head);
+    head.contains("<svg") || head.trim_start().starts_with("<?xml")
+}
+
+/// Decodes an encoded image held in memory. `format` is "png", "jpeg"/"jpg"
+/// or "svg"; without it the format is detected from the data.
+pub fn image_from_bytes(bytes: &[u8], format: Option<&str>) -> Result<Image, String> {
+    let format = format.map(|f| f.trim().to_lowercase());
+    let is_svg = match format.as_deref() {
+        Some("svg") => true,
+        Some("png" | "jpeg" | "jpg") => false,
+        Some(other) => return Err(format!("Unsupported image format '{}'", other)),
+        None => looks_like_svg(bytes),
+    };
+
+    if is_svg {
+        return Image::load_from_svg_data(bytes)
+            .map_err(|e| format!("Failed to decode SVG image: {:?}", e));
+    }
+
+    let decoded = match format.as_deref() {
+        Some("png") => image::load_from_memory_with_format(bytes, image::ImageFormat::Png),
+        Some("jpeg" | "jpg") => {
+            image::load_from_memory_with_format(bytes, image::ImageFormat::Jpeg)
+        }
+        _ => image::load_from_memory(bytes),
+    }
+    .map_err(|e| format!("Failed to decode image: {}", e))?
+    .into_rgba8();
+
+    let (width, height) = decoded.dimensions();
+    let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(decoded.as_raw(), width, height);
+    Ok(Image::from_rgba8(buffer))
+}
+
+fn check_pixel_count(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    bytes_per_pixel: usize,
+) -> Result<(), String> {
+    if width == 0 || height == 0 {
+        return Err("Image width and height must be greater than 0".to_string());
+    }
+    let expected = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|count| count.checked_mul(bytes_per_pixel))
+        .ok_or_else(|| format!("Image size {}x{} is too large", width, height))?;
+    if pixels.len() != expected {
+        return Err(format!(
+            "Expected {} bytes for a {}x{} image ({} per pixel), got {}",
+            expected,
+            width,
+            height,
+            bytes_per_pixel,
+            pixels.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Builds an image from raw RGBA8 pixels, row by row from the top left.
+pub fn image_from_rgba8(pixels: &[u8], width: u32, height: u32) -> Result<Image, String> {
+    check_pixel_count(pixels, width, height, 4)?;
+    let buffer = SharedPixelBuffer::<Rgba8Pixel>::clone_from_slice(pixels, width, height);
+    Ok(Image::from_rgba8(buffer))
+}
+
+/// Builds an image from raw RGB8 pixels, row by row from the top left.
+pub fn image_from_rgb8(pixels: &[u8], width: u32, height: u32) -> Result<Image, String> {
+    check_pixel_count(pixels, width, height, 3)?;
+    let buffer = SharedPixelBuffer::<Rgb8Pixel>::clone_from_slice(pixels, width, height);
+    Ok(Image::from_rgb8(buffer))
+}
+
+pub fn get_image_property(instance: &ComponentInstance, name: &str) -> Result<Image, String> {
+    match instance_get_property(instance, name)? {
+        Value::Image(image) => Ok(image),
+        other => Err(format!(
+            "Property '{}' is not an image (got {:?})",
+            name,
+            other.value_type()
+        )),
+    }
+}
+
+/// Width and height of an image in pixels.
+pub fn image_size(image: &Image) -> (u32, u32) {
+    let size = image.size();
+    (size.width, size.height)
+}
+
+/// The image's pixels as RGBA8 bytes, row by row from the top left.
+pub fn image_to_rgba8(image: &Image) -> Result<Vec<u8>, String> {
+    let buffer = image
+        .to_rgba8()
+        .ok_or_else(|| "Image has no pixel data to read back".to_string())?;
+    Ok(buffer.as_bytes().to_vec())
+}
+
+/// The image re-encoded as PNG.
+pub fn image_to_png(image: &Image) -> Result<Vec<u8>, String> {
+    let (width, height) = image_size(image);
+    let pixels = image_to_rgba8(image)?;
+    let rgba = image::RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| "Image pixel data does not match its size".to_string())?;
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    rgba.write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(png.into_inner())
+}
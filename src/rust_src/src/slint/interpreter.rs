@@ -1,12 +1,31 @@
+use crate::slint::{SlintCompilationResult, SlintComponentDef};
+use i_slint_compiler::langtype::Type as LangType;
 use once_cell::sync::Lazy;
-use slint_interpreter::{Compiler, ComponentDefinition};
+use slint_interpreter::{CompilationResult, Compiler, ComponentDefinition, DiagnosticLevel};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Mutex;
 
 static SLINT_STYLE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
 static SLINT_LIBRARY_PATHS: Lazy<Mutex<HashMap<String, PathBuf>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
+static SLINT_INCLUDE_PATHS: Lazy<Mutex<Vec<PathBuf>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static SLINT_VIRTUAL_FILES: Lazy<Mutex<HashMap<PathBuf, String>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static LAST_DIAGNOSTICS: Lazy<Mutex<Vec<CompileDiagnostic>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static LAST_LOADED_FILES: Lazy<Mutex<Vec<PathBuf>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static LOADED_FILES_BY_PATH: Lazy<Mutex<HashMap<String, Vec<PathBuf>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A compiler message from the last build, detached from the `CompilationResult`.
+#[derive(Clone, Debug)]
+pub struct CompileDiagnostic {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub severity: &'static str,
+    pub message: String,
+}
 
 pub fn set_style(style: &str) {
     let mut guard = SLINT_STYLE.lock().unwrap_or_else(|e| e.into_inner());
@@ -45,6 +64,77 @@ pub fn clear_library_paths() {
     guard.clear();
 }
 
+pub fn add_include_path(path: &str) {
+    let mut guard = SLINT_INCLUDE_PATHS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let path = PathBuf::from(path);
+    if !guard.contains(&path) {
+        guard.push(path);
+    }
+}
+
+pub fn remove_include_path(path: &str) {
+    let mut guard = SLINT_INCLUDE_PATHS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    guard.retain(|p| p != Path::new(path));
+}
+
+pub fn clear_include_paths() {
+    let mut guard = SLINT_INCLUDE_PATHS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    guard.clear();
+}
+
+/// Lexically cleans a path (drops `.` and folds `..`) so that virtual files
+/// match the paths the compiler resolves imports to.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !result.pop() {
+                    result.push("..");
+                }
+            }
+            other => result.push(other.as_os_str()),
+        }
+    }
+    result
+}
+
+pub fn add_virtual_file(path: &str, source: &str) {
+    let mut guard = SLINT_VIRTUAL_FILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    guard.insert(normalize_path(Path::new(path)), source.to_string());
+}
+
+pub fn remove_virtual_file(path: &str) {
+    let mut guard = SLINT_VIRTUAL_FILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    guard.remove(&normalize_path(Path::new(path)));
+}
+
+pub fn clear_virtual_files() {
+    let mut guard = SLINT_VIRTUAL_FILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    guard.clear();
+}
+
+fn virtual_file_source(path: &Path) -> Option<String> {
+    SLINT_VIRTUAL_FILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&normalize_path(path))
+        .cloned()
+}
+
 fn create_compiler() -> Compiler {
     let mut compiler = Compiler::new();
     if let Some(style) = SLINT_STYLE
@@ -63,87 +153,386 @@ fn create_compiler() -> Compiler {
         compiler.set_library_paths(library_paths);
     }
 
+    #[allow(unused_mut)]
+    let mut include_paths: Vec<PathBuf> = Vec::new();
+
     #[cfg(target_os = "android")]
     if let Some(assets_dir) = crate::android::get_assets_dir() {
-        compiler.set_include_paths(vec![assets_dir.clone()]);
         log::debug!("Set include path to: {}", assets_dir.display());
+        include_paths.push(assets_dir);
+    }
+
+    include_paths.extend(
+        SLINT_INCLUDE_PATHS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned(),
+    );
+    if !include_paths.is_empty() {
+        compiler.set_include_paths(include_paths);
     }
 
+    LAST_LOADED_FILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+    compiler.set_file_loader(|path| {
+        record_loaded_file(path.to_path_buf());
+        let source = virtual_file_source(path);
+        Box::pin(async move { source.map(Ok::<String, std::io::Error>) })
+    });
+
     compiler
 }
 
-pub fn compile_from_path(path: &str) -> Result<ComponentDefinition, String> {
+fn record_loaded_file(path: PathBuf) {
+    let mut guard = LAST_LOADED_FILES.lock().unwrap_or_else(|e| e.into_inner());
+    if !guard.contains(&path) {
+        guard.push(path);
+    }
+}
+
+/// Files read by the last build: the main file and every file it imports.
+pub fn last_loaded_files() -> Vec<PathBuf> {
+    LAST_LOADED_FILES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Files read by the last build of `path`, whatever was built since.
+pub fn loaded_files_of(path: &str) -> Option<Vec<PathBuf>> {
+    LOADED_FILES_BY_PATH
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(path)
+        .cloned()
+}
+
+fn severity_name(level: DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+        _ => "note",
+    }
+}
+
+fn collect_diagnostics(result: &CompilationResult) -> Vec<CompileDiagnostic> {
+    result
+        .diagnostics()
+        .map(|d| {
+            let (line, column) = d.line_column();
+            CompileDiagnostic {
+                file: d
+                    .source_file()
+                    .map(|f| f.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                line,
+                column,
+                severity: severity_name(d.level()),
+                message: d.message().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Stores every diagnostic of `result` for `last_diagnostics()` and turns
+/// errors into a single message suitable for `ring_error!`.
+fn record_diagnostics(result: &CompilationResult) -> Result<(), String> {
+    let diagnostics = collect_diagnostics(result);
+
+    let errors: Vec<String> = diagnostics
+        .iter()
+        .filter(|d| d.severity == "error")
+        .map(|d| format!("{}:{}:{}: {}", d.file, d.line, d.column, d.message))
+        .collect();
+
+    *LAST_DIAGNOSTICS.lock().unwrap_or_else(|e| e.into_inner()) = diagnostics;
+
+    if result.has_errors() {
+        return Err(errors.join("\n"));
+    }
+    Ok(())
+}
+
+pub fn last_diagnostics() -> Vec<CompileDiagnostic> {
+    LAST_DIAGNOSTICS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Adds an error found after the compiler ran, like an instance that hot
+/// reload could not recreate, to `last_diagnostics()`.
+pub fn report_diagnostic(file: &str, message: String) {
+    LAST_DIAGNOSTICS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(CompileDiagnostic {
+            file: file.to_string(),
+            line: 0,
+            column: 0,
+            severity: "error",
+            message,
+        });
+}
+
+fn resolve_path(path: &str) -> PathBuf {
     #[cfg(target_os = "android")]
-    let actual_path = {
+    {
         if let Some(assets_dir) = crate::android::get_assets_dir() {
             let full_path = assets_dir.join(path);
             log::debug!("Android: loading from {}", full_path.display());
-            full_path
-        } else {
-            PathBuf::from(path)
+            return full_path;
         }
+    }
+
+    PathBuf::from(path)
+}
+
+/// Builds `path`, reading it from the registered virtual files when present.
+fn build_path_result(path: &str) -> CompilationResult {
+    let compiler = create_compiler();
+    let actual_path = resolve_path(path);
+    record_loaded_file(actual_path.clone());
+    let result = match virtual_file_source(&actual_path) {
+        Some(source) => spin_on::spin_on(compiler.build_from_source(source, actual_path)),
+        None => spin_on::spin_on(compiler.build_from_path(actual_path)),
     };
+    LOADED_FILES_BY_PATH
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(path.to_string(), last_loaded_files());
+    result
+}
 
-    #[cfg(not(target_os = "android"))]
-    let actual_path = PathBuf::from(path);
+fn build_from_path(path: &str) -> Result<CompilationResult, String> {
+    let result = build_path_result(path);
+    record_diagnostics(&result)?;
+    Ok(result)
+}
 
+fn build_from_source(source: &str, path: &str) -> Result<CompilationResult, String> {
     let compiler = create_compiler();
-    let result = spin_on::spin_on(compiler.build_from_path(&actual_path));
+    let result =
+        spin_on::spin_on(compiler.build_from_source(source.to_string(), PathBuf::from(path)));
+    record_diagnostics(&result)?;
+    Ok(result)
+}
 
-    if result.has_errors() {
-        let errors: Vec<String> = result
-            .diagnostics()
-            .map(|d| format!("{}: {}", d.line_column().0, d.message()))
-            .collect();
-        return Err(errors.join("\n"));
-    }
+/// Picks `name` (or the first exported component) and keeps every other
+/// component of the same build alongside it.
+fn select_component(
+    result: &CompilationResult,
+    name: Option<&str>,
+    origin: &str,
+) -> Result<SlintComponentDef, String> {
+    let components: Vec<ComponentDefinition> = result.components().collect();
+    let definition = match name {
+        Some(name) => components
+            .iter()
+            .find(|c| c.name() == name)
+            .cloned()
+            .ok_or_else(|| format!("Component '{}' not found in {}", name, origin))?,
+        None => components
+            .first()
+            .cloned()
+            .ok_or_else(|| format!("No component found in {}", origin))?,
+    };
+    Ok(SlintComponentDef {
+        definition,
+        components,
+    })
+}
 
-    let name = result
-        .component_names()
-        .next()
-        .ok_or_else(|| format!("No component found in '{}'", path))?;
-    result
-        .component(name)
-        .ok_or_else(|| format!("Failed to get component '{}' from '{}'", name, path))
+pub fn compile_from_path(path: &str) -> Result<SlintComponentDef, String> {
+    let result = build_from_path(path)?;
+    select_component(&result, None, &format!("'{}'", path))
+}
+
+pub fn compile_from_source(source: &str, path: &str) -> Result<SlintComponentDef, String> {
+    let result = build_from_source(source, path)?;
+    select_component(&result, None, &format!("source (path: '{}')", path))
+}
+
+pub fn get_component_by_name(path: &str, name: &str) -> Result<SlintComponentDef, String> {
+    let result = build_from_path(path)?;
+    select_component(&result, Some(name), &format!("'{}'", path))
+}
+
+pub fn get_component_by_name_from_source(
+    source: &str,
+    path: &str,
+    name: &str,
+) -> Result<SlintComponentDef, String> {
+    let result = build_from_source(source, path)?;
+    select_component(&result, Some(name), &format!("source (path: '{}')", path))
+}
+
+pub fn component_names(component: &SlintComponentDef) -> Vec<String> {
+    component
+        .components
+        .iter()
+        .map(|c| c.name().to_string())
+        .collect()
+}
+
+/// Returns another component from the build `component` came from, without recompiling.
+pub fn sibling_component(
+    component: &SlintComponentDef,
+    name: &str,
+) -> Result<SlintComponentDef, String> {
+    let definition = component
+        .components
+        .iter()
+        .find(|c| c.name() == name)
+        .cloned()
+        .ok_or_else(|| format!("Component '{}' not found", name))?;
+    Ok(SlintComponentDef {
+        definition,
+        components: component.components.clone(),
+    })
 }
 
-pub fn compile_from_source(source: &str, path: &str) -> Result<ComponentDefinition, String> {
+/// Compiles `path` and keeps the whole result, even when it has errors,
+/// so that diagnostics can be inspected from Ring.
+pub fn compile_result_from_path(path: &str) -> SlintCompilationResult {
+    let result = build_path_result(path);
+    let _ = record_diagnostics(&result);
+    SlintCompilationResult { result }
+}
+
+pub fn compile_result_from_source(source: &str, path: &str) -> SlintCompilationResult {
     let compiler = create_compiler();
     let result =
         spin_on::spin_on(compiler.build_from_source(source.to_string(), PathBuf::from(path)));
+    let _ = record_diagnostics(&result);
+    SlintCompilationResult { result }
+}
 
-    if result.has_errors() {
-        let errors: Vec<String> = result
-            .diagnostics()
-            .map(|d| format!("{}: {}", d.line_column().0, d.message()))
-            .collect();
-        return Err(errors.join("\n"));
-    }
+pub fn result_has_errors(compiled: &SlintCompilationResult) -> bool {
+    compiled.result.has_errors()
+}
 
-    let name = result
+pub fn result_diagnostics(compiled: &SlintCompilationResult) -> Vec<CompileDiagnostic> {
+    collect_diagnostics(&compiled.result)
+}
+
+pub fn result_component_names(compiled: &SlintCompilationResult) -> Vec<String> {
+    compiled
+        .result
         .component_names()
-        .next()
-        .ok_or_else(|| format!("No component found in source (path: '{}')", path))?;
-    result
-        .component(name)
-        .ok_or_else(|| format!("Failed to get component '{}' from source", name))
+        .map(|name| name.to_string())
+        .collect()
 }
 
-pub fn get_component_by_name(path: &str, name: &str) -> Result<ComponentDefinition, String> {
-    let compiler = create_compiler();
-    let result = spin_on::spin_on(compiler.build_from_path(path));
+pub fn result_component(
+    compiled: &SlintCompilationResult,
+    name: &str,
+) -> Result<SlintComponentDef, String> {
+    if compiled.result.has_errors() {
+        return Err("Compilation result has errors".to_string());
+    }
+    select_component(&compiled.result, Some(name), "compilation result")
+}
 
-    if result.has_errors() {
-        let errors: Vec<String> = result
-            .diagnostics()
-            .map(|d| format!("{}: {}", d.line_column().0, d.message()))
-            .collect();
-        return Err(errors.join("\n"));
+/// Names of the structs and enums exported by the build, paired with their kind.
+pub fn result_types(compiled: &SlintCompilationResult) -> Vec<(String, &'static str)> {
+    compiled
+        .result
+        .structs_and_enums(i_slint_core::InternalToken)
+        .filter_map(|ty| match ty {
+            LangType::Struct(_) => Some((ty.to_string(), "struct")),
+            LangType::Enumeration(e) => Some((e.name.to_string(), "enum")),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Enums exported by the build.
+pub fn result_enums(compiled: &SlintCompilationResult) -> Vec<LangType> {
+    compiled
+        .result
+        .structs_and_enums(i_slint_core::InternalToken)
+        .filter(|ty| matches!(ty, LangType::Enumeration(_)))
+        .cloned()
+        .collect()
+}
+
+/// Structs exported by the build.
+pub fn result_structs(compiled: &SlintCompilationResult) -> Vec<LangType> {
+    compiled
+        .result
+        .structs_and_enums(i_slint_core::InternalToken)
+        .filter(|ty| matches!(ty, LangType::Struct(_)))
+        .cloned()
+        .collect()
+}
+
+/// Compares Slint identifiers, which treat `-` and `_` as the same character.
+pub fn same_identifier(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.chars()
+            .zip(b.chars())
+            .all(|(x, y)| x == y || (x == '-' || x == '_') && (y == '-' || y == '_'))
+}
+
+/// Declared type of a property, callback or function on the component, or
+/// on `global` when given.
+fn declared_type(
+    definition: &ComponentDefinition,
+    global: Option<&str>,
+    name: &str,
+) -> Option<LangType> {
+    let find = |(item, (ty, _)): (_, (LangType, _))| {
+        let item: &str = &item;
+        same_identifier(item, name).then_some(ty)
+    };
+
+    match global {
+        Some(global) => definition
+            .global_properties_and_callbacks(global)
+            .and_then(|mut items| items.find_map(find)),
+        None => definition.properties_and_callbacks().find_map(find),
     }
+}
 
-    result
-        .component(name)
-        .ok_or_else(|| format!("Component '{}' not found", name))
+/// Declared type of a property, used to convert Ring values exactly.
+pub fn property_type(
+    definition: &ComponentDefinition,
+    global: Option<&str>,
+    property_name: &str,
+) -> Option<LangType> {
+    declared_type(definition, global, property_name)
+        .filter(|ty| !matches!(ty, LangType::Callback(_) | LangType::Function(_)))
+}
+
+/// Declared argument types of a callback or function.
+pub fn callback_arg_types(
+    definition: &ComponentDefinition,
+    global: Option<&str>,
+    callback_name: &str,
+) -> Option<Vec<LangType>> {
+    match declared_type(definition, global, callback_name)? {
+        LangType::Callback(function) | LangType::Function(function) => Some(function.args.clone()),
+        _ => None,
+    }
+}
+
+/// Declared return type of a callback on the component, or on `global` when given.
+/// Unknown callbacks report `Void` so their handlers keep returning nothing.
+pub fn callback_return_type(
+    definition: &ComponentDefinition,
+    global: Option<&str>,
+    callback_name: &str,
+) -> LangType {
+    match declared_type(definition, global, callback_name) {
+        Some(LangType::Callback(function)) | Some(LangType::Function(function)) => {
+            function.return_type.clone()
+        }
+        _ => LangType::Void,
+    }
 }
 
 pub fn definition_name(definition: &ComponentDefinition) -> String {
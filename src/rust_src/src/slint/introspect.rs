@@ -0,0 +1,126 @@
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_compiler::object_tree::PropertyVisibility;
+use ring_lang_rs::*;
+use slint_interpreter::ComponentDefinition;
+
+fn add_string_pair(list: RingList, key: &str, value: &str) {
+    let pair = ring_list_newlist(list);
+    ring_list_addstring_str(pair, key);
+    ring_list_addstring_str(pair, value);
+}
+
+/// Adds `[key, []]` to a hash list and returns the inner list.
+fn add_list_pair(list: RingList, key: &str) -> RingList {
+    let pair = ring_list_newlist(list);
+    ring_list_addstring_str(pair, key);
+    ring_list_newlist(pair)
+}
+
+/// "in", "out" or "in-out" from the compiler's visibility, and "private" for
+/// the rest, including variants later compiler versions add.
+fn visibility_name(visibility: PropertyVisibility) -> &'static str {
+    match visibility {
+        PropertyVisibility::Input => "in",
+        PropertyVisibility::Output => "out",
+        PropertyVisibility::InOut => "in-out",
+        _ => "private",
+    }
+}
+
+/// Fills `list` with a hash describing a Slint type:
+/// `:kind` ("int", "string", "struct", "array", "enum", ...) and `:name` (the
+/// type as written in .slint), plus `:fields` for structs, `:element` for
+/// arrays and `:values` and `:default` for enums.
+pub fn add_type_description(list: RingList, ty: &LangType) {
+    let kind = match ty {
+        LangType::Struct(_) => "struct".to_string(),
+        LangType::Array(_) => "array".to_string(),
+        LangType::Enumeration(_) => "enum".to_string(),
+        LangType::Callback(_) => "callback".to_string(),
+        LangType::Function(_) => "function".to_string(),
+        other => other.to_string(),
+    };
+    add_string_pair(list, "kind", &kind);
+
+    match ty {
+        LangType::Struct(s) => {
+            add_string_pair(list, "name", &ty.to_string());
+            let fields = add_list_pair(list, "fields");
+            for (name, field_ty) in s.fields.iter() {
+                let field = ring_list_newlist(fields);
+                add_string_pair(field, "name", name);
+                add_type_description(add_list_pair(field, "type"), field_ty);
+            }
+        }
+        LangType::Array(element) => {
+            add_string_pair(list, "name", &ty.to_string());
+            add_type_description(add_list_pair(list, "element"), element);
+        }
+        LangType::Enumeration(e) => {
+            add_string_pair(list, "name", &e.name);
+            let values = add_list_pair(list, "values");
+            for value in e.values.iter() {
+                ring_list_addstring_str(values, value);
+            }
+            if let Some(default) = e.values.get(e.default_value) {
+                add_string_pair(list, "default", default);
+            }
+        }
+        _ => add_string_pair(list, "name", &ty.to_string()),
+    }
+}
+
+/// Fills `list` with `:name`, `:args` and `:return` for a callback or function.
+fn add_signature(list: RingList, name: &str, ty: &LangType) {
+    add_string_pair(list, "name", name);
+    let (LangType::Callback(function) | LangType::Function(function)) = ty else {
+        return;
+    };
+    let args = add_list_pair(list, "args");
+    for arg in function.args.iter() {
+        add_type_description(ring_list_newlist(args), arg);
+    }
+    add_type_description(add_list_pair(list, "return"), &function.return_type);
+}
+
+/// Adds `:properties`, `:callbacks` and `:functions` for one component or global.
+fn add_members<N: AsRef<str>>(
+    list: RingList,
+    members: impl Iterator<Item = (N, (LangType, PropertyVisibility))>,
+) {
+    let properties = add_list_pair(list, "properties");
+    let callbacks = add_list_pair(list, "callbacks");
+    let functions = add_list_pair(list, "functions");
+
+    for (name, (ty, visibility)) in members {
+        let name = name.as_ref();
+        match ty {
+            LangType::Callback(_) => add_signature(ring_list_newlist(callbacks), name, &ty),
+            LangType::Function(_) => add_signature(ring_list_newlist(functions), name, &ty),
+            _ => {
+                let property = ring_list_newlist(properties);
+                add_string_pair(property, "name", name);
+                add_type_description(add_list_pair(property, "type"), &ty);
+                add_string_pair(property, "visibility", visibility_name(visibility));
+            }
+        }
+    }
+}
+
+/// Fills `list` with a hash describing a component: `:name`, `:properties`,
+/// `:callbacks`, `:functions` and `:globals`, where each global has the same
+/// members.
+pub fn add_definition_description(list: RingList, definition: &ComponentDefinition) {
+    add_string_pair(list, "name", definition.name());
+    add_members(list, definition.properties_and_callbacks());
+
+    let globals = add_list_pair(list, "globals");
+    for global in definition.globals() {
+        let Some(members) = definition.global_properties_and_callbacks(&global) else {
+            continue;
+        };
+        let entry = ring_list_newlist(globals);
+        add_string_pair(entry, "name", &global);
+        add_members(entry, members);
+    }
+}
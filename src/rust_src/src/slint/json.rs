@@ -0,0 +1,153 @@
+use crate::slint::{Item, ItemList, color_to_hex, f32_to_ring, item_to_typed_value};
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::graphics::{Brush, GradientStop};
+use i_slint_core::model::Model;
+use serde_json::{Value as Json, json};
+use slint_interpreter::{Struct, Value};
+
+/// Serialises a Slint value. Enums become their variant name, colors hex
+/// strings, gradients `{type, angle, stops}` objects and images their file
+/// path, or null when they were not loaded from a file.
+pub fn value_to_json(value: &Value) -> Json {
+    match value {
+        Value::Void => Json::Null,
+        Value::Number(n) => number_to_json(*n),
+        Value::String(s) => Json::String(s.to_string()),
+        Value::Bool(b) => Json::Bool(*b),
+        Value::Image(img) => img
+            .path()
+            .map_or(Json::Null, |path| path.to_string_lossy().into()),
+        Value::Model(model) => Json::Array(
+            (0..model.row_count())
+                .filter_map(|row| model.row_data(row))
+                .map(|row| value_to_json(&row))
+                .collect(),
+        ),
+        Value::Struct(s) => Json::Object(
+            s.iter()
+                .map(|(key, value)| (key.to_string(), value_to_json(value)))
+                .collect(),
+        ),
+        Value::Brush(brush) => brush_to_json(brush),
+        Value::EnumerationValue(_, variant) => Json::String(variant.clone()),
+        _ => Json::Null,
+    }
+}
+
+pub fn value_to_json_string(value: &Value) -> String {
+    value_to_json(value).to_string()
+}
+
+/// Parses `text` and converts it to `ty`, or guesses the Slint types from the
+/// JSON ones when the type is unknown.
+pub fn json_string_to_value(text: &str, ty: Option<&LangType>) -> Result<Value, String> {
+    let json: Json = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    match ty {
+        Some(ty) => item_to_typed_value(json_item(&json), ty),
+        None => Ok(untyped_json_value(&json)),
+    }
+}
+
+fn number_to_json(n: f64) -> Json {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        Json::from(n as i64)
+    } else {
+        Json::from(n)
+    }
+}
+
+fn brush_to_json(brush: &Brush) -> Json {
+    let stops = |stops: Vec<&GradientStop>| -> Json {
+        stops
+            .into_iter()
+            .map(|stop| {
+                json!({
+                    "color": color_to_hex(stop.color),
+                    "position": f32_to_ring(stop.position),
+                })
+            })
+            .collect()
+    };
+    match brush {
+        Brush::LinearGradient(g) => json!({
+            "type": "linear",
+            "angle": f32_to_ring(g.angle()),
+            "stops": stops(g.stops().collect()),
+        }),
+        Brush::RadialGradient(g) => json!({
+            "type": "radial",
+            "stops": stops(g.stops().collect()),
+        }),
+        _ => Json::String(color_to_hex(brush.color())),
+    }
+}
+
+fn untyped_json_value(json: &Json) -> Value {
+    match json {
+        Json::Null => Value::Void,
+        Json::Bool(b) => Value::Bool(*b),
+        Json::Number(n) => Value::Number(n.as_f64().unwrap_or_default()),
+        Json::String(s) => Value::String(s.as_str().into()),
+        Json::Array(rows) => {
+            let rows: Vec<Value> = rows.iter().map(untyped_json_value).collect();
+            Value::Model(rows.as_slice().into())
+        }
+        Json::Object(fields) => Value::Struct(Struct::from_iter(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), untyped_json_value(value))),
+        )),
+    }
+}
+
+fn json_item(json: &Json) -> Item<&Json> {
+    match json {
+        Json::Null => Item::Null,
+        Json::Bool(b) => Item::Bool(*b),
+        Json::Number(n) => Item::Number(n.as_f64().unwrap_or_default()),
+        Json::String(s) => Item::String(s.clone()),
+        Json::Array(_) | Json::Object(_) => Item::List(json),
+    }
+}
+
+/// Lets the typed conversion of Ring values read JSON arrays and objects.
+impl ItemList for &Json {
+    const HASH_NAME: &'static str = "an object";
+
+    fn describe(self) -> &'static str {
+        match self {
+            Json::Object(_) => "an object",
+            _ => "an array",
+        }
+    }
+
+    fn items(self) -> Option<Vec<Item<Self>>> {
+        match self {
+            Json::Array(items) => Some(items.iter().map(json_item).collect()),
+            _ => None,
+        }
+    }
+
+    fn pairs(self) -> Option<Vec<(String, Item<Self>)>> {
+        match self {
+            Json::Object(fields) => Some(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), json_item(value)))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn untyped_value(item: Item<Self>) -> Value {
+        match item {
+            Item::Number(n) => Value::Number(n),
+            Item::String(s) => Value::String(s.into()),
+            Item::Bool(b) => Value::Bool(b),
+            Item::List(json) => untyped_json_value(json),
+            Item::Value(value) => value,
+            Item::Null | Item::Other => Value::Void,
+        }
+    }
+}
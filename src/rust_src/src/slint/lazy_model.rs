@@ -0,0 +1,348 @@
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::model::{Model, ModelNotify, ModelRc, ModelTracker};
+use ring_lang_rs::RingVM;
+use slint_interpreter::{ComponentInstance, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::slint::{RingHandler, property_type, run_ring_handler};
+
+const DEFAULT_CACHE_SIZE: usize = 256;
+
+thread_local! {
+    static LAZY_MODELS: RefCell<HashMap<u32, Rc<LazyModel>>> = RefCell::new(HashMap::new());
+    static NEXT_LAZY_MODEL_ID: RefCell<u32> = const { RefCell::new(1) };
+}
+
+/// A model whose rows live on the Ring side: the row count and each row are
+/// asked from Ring functions when Slint needs them, so a `ListView` only
+/// fetches the rows it shows. Ring reports changes to its data with the
+/// notify functions below.
+struct LazyModel {
+    vm: RingVM,
+    count_handler: RingHandler,
+    row_handler: RingHandler,
+    row_type: LangType,
+    /// Asked from Ring again after a reset.
+    count: Cell<Option<usize>>,
+    cache: RefCell<RowCache>,
+    /// Set once the model is destroyed, so the property shows no rows instead
+    /// of calling released Ring functions.
+    detached: Cell<bool>,
+    notify: ModelNotify,
+}
+
+/// The most recently used rows, so scrolling back and repaints don't call
+/// Ring again. The rows are linked from the least to the most recently used,
+/// so using and evicting a row doesn't scan the others.
+struct RowCache {
+    capacity: usize,
+    rows: HashMap<usize, CachedRow>,
+    oldest: Option<usize>,
+    newest: Option<usize>,
+}
+
+struct CachedRow {
+    value: Value,
+    older: Option<usize>,
+    newer: Option<usize>,
+}
+
+impl RowCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            rows: HashMap::new(),
+            oldest: None,
+            newest: None,
+        }
+    }
+
+    fn get(&mut self, row: usize) -> Option<Value> {
+        let value = self.unlink(row)?;
+        self.push_newest(row, value.clone());
+        Some(value)
+    }
+
+    fn insert(&mut self, row: usize, value: Value) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.unlink(row);
+        while self.rows.len() >= self.capacity {
+            self.evict_oldest();
+        }
+        self.push_newest(row, value);
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.rows.len() > capacity {
+            self.evict_oldest();
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(oldest) = self.oldest {
+            self.unlink(oldest);
+        }
+    }
+
+    fn push_newest(&mut self, row: usize, value: Value) {
+        match self.newest {
+            Some(newest) => {
+                if let Some(entry) = self.rows.get_mut(&newest) {
+                    entry.newer = Some(row);
+                }
+            }
+            None => self.oldest = Some(row),
+        }
+        self.rows.insert(
+            row,
+            CachedRow {
+                value,
+                older: self.newest,
+                newer: None,
+            },
+        );
+        self.newest = Some(row);
+    }
+
+    /// Removes a row from the cache and the recency list, returning its value.
+    fn unlink(&mut self, row: usize) -> Option<Value> {
+        let entry = self.rows.remove(&row)?;
+        match entry.older {
+            Some(older) => {
+                if let Some(older) = self.rows.get_mut(&older) {
+                    older.newer = entry.newer;
+                }
+            }
+            None => self.oldest = entry.newer,
+        }
+        match entry.newer {
+            Some(newer) => {
+                if let Some(newer) = self.rows.get_mut(&newer) {
+                    newer.older = entry.older;
+                }
+            }
+            None => self.newest = entry.older,
+        }
+        Some(entry.value)
+    }
+
+    fn forget(&mut self, rows: std::ops::Range<usize>) {
+        self.forget_where(|row| rows.contains(&row));
+    }
+
+    /// Drops every row from `index` on, whose indexes shift when rows are
+    /// added or removed before them.
+    fn forget_from(&mut self, index: usize) {
+        if index == 0 {
+            self.rows.clear();
+            self.oldest = None;
+            self.newest = None;
+        } else {
+            self.forget_where(|row| row >= index);
+        }
+    }
+
+    fn forget_where(&mut self, forget: impl Fn(usize) -> bool) {
+        let rows: Vec<usize> = self
+            .rows
+            .keys()
+            .copied()
+            .filter(|&row| forget(row))
+            .collect();
+        for row in rows {
+            self.unlink(row);
+        }
+    }
+}
+
+impl LazyModel {
+    fn len(&self) -> usize {
+        if self.detached.get() {
+            return 0;
+        }
+        if let Some(count) = self.count.get() {
+            return count;
+        }
+        let count = match run_ring_handler(self.vm, &self.count_handler, &[], &LangType::Int32) {
+            Value::Number(n) if n > 0.0 => n as usize,
+            _ => 0,
+        };
+        self.count.set(Some(count));
+        count
+    }
+}
+
+impl Model for LazyModel {
+    type Data = Value;
+
+    fn row_count(&self) -> usize {
+        self.len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Value> {
+        if row >= self.len() {
+            return None;
+        }
+        if let Some(value) = self.cache.borrow_mut().get(row) {
+            return Some(value);
+        }
+        // The cache is not borrowed while Ring runs, which may notify changes.
+        let args = [Value::Number(row as f64)];
+        let value = run_ring_handler(self.vm, &self.row_handler, &args, &self.row_type);
+        self.cache.borrow_mut().insert(row, value.clone());
+        Some(value)
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.notify
+    }
+}
+
+fn lazy_model(model_id: u32) -> Result<Rc<LazyModel>, String> {
+    LAZY_MODELS.with(|models| {
+        models
+            .borrow()
+            .get(&model_id)
+            .cloned()
+            .ok_or_else(|| format!("Lazy model {} not found", model_id))
+    })
+}
+
+/// Checks that `index..index + count` lies within `size` rows.
+fn check_range(model_id: u32, index: usize, count: usize, size: usize) -> Result<(), String> {
+    if index.checked_add(count).is_some_and(|end| end <= size) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Range {}..{} out of bounds for lazy model {} (size: {})",
+            index,
+            index.saturating_add(count),
+            model_id,
+            size
+        ))
+    }
+}
+
+pub fn lazy_model_create(
+    instance: &ComponentInstance,
+    property_name: &str,
+    vm: RingVM,
+    count_handler: &str,
+    row_handler: &str,
+) -> Result<u32, String> {
+    let row_type = match property_type(&instance.definition(), None, property_name) {
+        Some(LangType::Array(row_type)) => row_type.as_ref().clone(),
+        Some(ty) => {
+            return Err(format!(
+                "Property '{}' is {}, not a model",
+                property_name, ty
+            ));
+        }
+        None => return Err(format!("Property '{}' not found", property_name)),
+    };
+
+    let model = Rc::new(LazyModel {
+        vm,
+        count_handler: RingHandler::new(count_handler),
+        row_handler: RingHandler::new(row_handler),
+        row_type,
+        count: Cell::new(None),
+        cache: RefCell::new(RowCache::new(DEFAULT_CACHE_SIZE)),
+        detached: Cell::new(false),
+        notify: ModelNotify::default(),
+    });
+    let model_rc: ModelRc<Value> = model.clone().into();
+    instance
+        .set_property(property_name, Value::Model(model_rc))
+        .map_err(|e| format!("Failed to bind lazy model to property: {:?}", e))?;
+
+    let id = NEXT_LAZY_MODEL_ID.with(|next| {
+        let id = *next.borrow();
+        *next.borrow_mut() = id + 1;
+        id
+    });
+    LAZY_MODELS.with(|models| models.borrow_mut().insert(id, model));
+
+    Ok(id)
+}
+
+/// Sets how many rows are kept; 0 asks Ring for a row every time Slint needs it.
+pub fn lazy_model_set_cache_size(model_id: u32, rows: usize) -> Result<(), String> {
+    lazy_model(model_id)?.cache.borrow_mut().set_capacity(rows);
+    Ok(())
+}
+
+/// Tells the views that `count` rows from `index` have new data.
+pub fn lazy_model_rows_changed(model_id: u32, index: usize, count: usize) -> Result<(), String> {
+    let model = lazy_model(model_id)?;
+    check_range(model_id, index, count, model.len())?;
+    model.cache.borrow_mut().forget(index..index + count);
+    for row in index..index + count {
+        model.notify.row_changed(row);
+    }
+    Ok(())
+}
+
+/// Tells the views that Ring inserted `count` rows at `index`.
+pub fn lazy_model_rows_added(model_id: u32, index: usize, count: usize) -> Result<(), String> {
+    let model = lazy_model(model_id)?;
+    // Ring's count already includes the new rows when no view asked for it yet.
+    let Some(size) = model.count.get() else {
+        return lazy_model_reset(model_id);
+    };
+    check_range(model_id, index, 0, size)?;
+    if count == 0 {
+        return Ok(());
+    }
+    model.count.set(Some(size + count));
+    model.cache.borrow_mut().forget_from(index);
+    model.notify.row_added(index, count);
+    Ok(())
+}
+
+/// Tells the views that Ring removed `count` rows from `index`.
+pub fn lazy_model_rows_removed(model_id: u32, index: usize, count: usize) -> Result<(), String> {
+    let model = lazy_model(model_id)?;
+    let Some(size) = model.count.get() else {
+        return lazy_model_reset(model_id);
+    };
+    check_range(model_id, index, count, size)?;
+    if count == 0 {
+        return Ok(());
+    }
+    model.count.set(Some(size - count));
+    model.cache.borrow_mut().forget_from(index);
+    model.notify.row_removed(index, count);
+    Ok(())
+}
+
+/// Forgets the count and every cached row, e.g. after a new query.
+pub fn lazy_model_reset(model_id: u32) -> Result<(), String> {
+    let model = lazy_model(model_id)?;
+    model.count.set(None);
+    model.cache.borrow_mut().forget_from(0);
+    model.notify.reset();
+    Ok(())
+}
+
+pub fn lazy_model_destroy(model_id: u32) -> Result<(), String> {
+    let removed = LAZY_MODELS.with(|models| models.borrow_mut().remove(&model_id));
+    match removed {
+        Some(model) => {
+            model.detached.set(true);
+            model.cache.borrow_mut().forget_from(0);
+            model.notify.reset();
+            model.count_handler.release(model.vm);
+            if model.row_handler.as_str() != model.count_handler.as_str() {
+                model.row_handler.release(model.vm);
+            }
+            Ok(())
+        }
+        None => Err(format!("Lazy model {} not found", model_id)),
+    }
+}
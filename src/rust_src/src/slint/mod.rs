@@ -3,58 +3,182 @@
 mod callback;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod clipboard;
+mod color;
 mod component;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod dialogs;
+mod handler;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod hotkey;
+mod image_data;
 mod interpreter;
+mod introspect;
+mod json;
+mod lazy_model;
 mod model;
+mod model_view;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod notification;
+mod reload;
 mod timer;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod tray;
 mod value;
+mod watch;
 
 pub use callback::*;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use clipboard::*;
+pub use color::*;
 pub use component::*;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use dialogs::*;
+pub use handler::*;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use hotkey::*;
+pub use image_data::*;
 pub use interpreter::*;
+pub use introspect::*;
+pub use json::*;
+pub use lazy_model::*;
 pub use model::*;
+pub use model_view::*;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use notification::*;
+pub use reload::*;
 pub use timer::*;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use tray::*;
 pub use value::*;
+pub use watch::*;
 
+use i_slint_compiler::langtype::Type as LangType;
 use slint_interpreter::{ComponentInstance, Value};
 use std::cell::RefCell;
 
 pub const SLINT_COMPONENT_TYPE: &[u8] = b"SlintComponent\0";
 pub const SLINT_INSTANCE_TYPE: &[u8] = b"SlintInstance\0";
+pub const SLINT_COMPILATION_TYPE: &[u8] = b"SlintCompilationResult\0";
+pub const SLINT_VALUE_TYPE: &[u8] = b"SlintValue\0";
+
+/// A whole build, so multi-window apps compile their UI once and create
+/// every component from it.
+pub struct SlintCompilationResult {
+    pub result: slint_interpreter::CompilationResult,
+}
+
+/// A Slint value built on the Rust side (e.g. an image decoded from bytes)
+/// that Ring passes around as an opaque pointer, on its own or inside lists.
+pub struct SlintValueWrapper {
+    pub value: Value,
+}
 
 pub struct SlintComponentDef {
     pub definition: slint_interpreter::ComponentDefinition,
+    /// Every exported component of the build `definition` came from.
+    pub components: Vec<slint_interpreter::ComponentDefinition>,
 }
 
 pub struct SlintInstanceWrapper {
     pub instance: ComponentInstance,
     pub vm_ptr: *mut libc::c_void,
+    pub bindings: RefCell<InstanceBindings>,
+}
+
+/// What Ring has set on an instance, so hot reload can replay it on a new one.
+#[derive(Default)]
+pub struct InstanceBindings {
+    pub properties: Vec<String>,
+    pub global_properties: Vec<(String, String)>,
+    pub callbacks: Vec<(String, RingHandler)>,
+    pub global_callbacks: Vec<(String, String, RingHandler)>,
 }
 
 impl SlintInstanceWrapper {
     pub fn new(instance: ComponentInstance, vm_ptr: *mut libc::c_void) -> Self {
-        Self { instance, vm_ptr }
+        Self {
+            instance,
+            vm_ptr,
+            bindings: RefCell::new(InstanceBindings::default()),
+        }
+    }
+
+    pub fn record_property(&self, name: &str) {
+        let mut bindings = self.bindings.borrow_mut();
+        if !bindings.properties.iter().any(|p| p == name) {
+            bindings.properties.push(name.to_string());
+        }
+    }
+
+    pub fn record_global_property(&self, global: &str, name: &str) {
+        let mut bindings = self.bindings.borrow_mut();
+        if !bindings
+            .global_properties
+            .iter()
+            .any(|(g, p)| g == global && p == name)
+        {
+            bindings
+                .global_properties
+                .push((global.to_string(), name.to_string()));
+        }
+    }
+
+    /// Handlers of every callback set on the instance, for releasing the
+    /// objects bound with slintMethod() when it is freed.
+    pub fn callback_handlers(&self) -> Vec<RingHandler> {
+        let bindings = self.bindings.borrow();
+        bindings
+            .callbacks
+            .iter()
+            .map(|(_, handler)| handler)
+            .chain(
+                bindings
+                    .global_callbacks
+                    .iter()
+                    .map(|(_, _, handler)| handler),
+            )
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the handler this one replaces, if it was a different one.
+    pub fn record_callback(&self, name: &str, handler: &RingHandler) -> Option<RingHandler> {
+        let mut bindings = self.bindings.borrow_mut();
+        let position = bindings.callbacks.iter().position(|(n, _)| n == name);
+        let replaced = position.map(|i| bindings.callbacks.remove(i).1);
+        bindings.callbacks.push((name.to_string(), handler.clone()));
+        replaced.filter(|old| old.as_str() != handler.as_str())
     }
+
+    /// Returns the handler this one replaces, if it was a different one.
+    pub fn record_global_callback(
+        &self,
+        global: &str,
+        name: &str,
+        handler: &RingHandler,
+    ) -> Option<RingHandler> {
+        let mut bindings = self.bindings.borrow_mut();
+        let position = bindings
+            .global_callbacks
+            .iter()
+            .position(|(g, n, _)| g == global && n == name);
+        let replaced = position.map(|i| bindings.global_callbacks.remove(i).2);
+        bindings
+            .global_callbacks
+            .push((global.to_string(), name.to_string(), handler.clone()));
+        replaced.filter(|old| old.as_str() != handler.as_str())
+    }
+}
+
+/// Arguments of one running Ring handler and the value it hands back to Slint.
+pub struct CallbackFrame {
+    pub args: Vec<Value>,
+    pub return_type: LangType,
+    pub returned: Option<Value>,
 }
 
 thread_local! {
-    pub static CALLBACK_ARGS: RefCell<Vec<Value>> = const { RefCell::new(Vec::new()) };
+    /// One frame per running handler, innermost last, so a handler that
+    /// triggers another callback keeps its own arguments.
+    pub static CALLBACK_FRAMES: RefCell<Vec<CallbackFrame>> = const { RefCell::new(Vec::new()) };
 }
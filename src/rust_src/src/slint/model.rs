@@ -1,11 +1,15 @@
-use i_slint_core::model::{Model, ModelRc, VecModel};
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::model::{Model, ModelNotify, ModelRc, ModelTracker};
 use ring_lang_rs::*;
 use slint_interpreter::{ComponentInstance, Value};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use crate::slint::ring_list_to_slint_model_or_struct;
+use crate::slint::{
+    RingHandler, property_type, ring_list_to_rows, ring_list_to_slint_model_or_struct,
+    ring_param_to_value, run_ring_handler, same_identifier,
+};
 
 thread_local! {
     static MODELS: RefCell<HashMap<u32, ModelHandle>> = RefCell::new(HashMap::new());
@@ -13,30 +17,190 @@ thread_local! {
 }
 
 struct ModelHandle {
-    model: Rc<VecModel<Value>>,
+    model: Rc<BoundModel>,
     property_name: String,
+    /// Declared element type of the bound property, when it is known.
+    row_type: Option<LangType>,
+    /// Called with the row index and new value when the UI edits a row.
+    on_change: Option<(RingVM, RingHandler)>,
+    /// Set for a model created in a row of another model.
+    parent: Option<ParentRow>,
 }
 
-pub fn model_create(instance: &ComponentInstance, property_name: &str) -> Result<u32, String> {
-    let model = Rc::new(VecModel::<Value>::default());
-    let model_rc: ModelRc<Value> = model.clone().into();
+/// The field of a row of another model that holds a nested model. The row
+/// follows inserts, removals and moves in the parent.
+struct ParentRow {
+    model_id: u32,
+    row: usize,
+    field: String,
+}
+
+/// The rows Ring manages, as bound to the property. Writes from Ring go
+/// through the inherent methods, each sending a single notification, and are
+/// not reported back; edits made by the UI (e.g. a two-way binding into
+/// `model[i].title`) go through `Model::set_row_data` and are reported to the
+/// model's change handler.
+struct BoundModel {
+    model_id: u32,
+    rows: RefCell<Vec<Value>>,
+    notify: ModelNotify,
+}
+
+impl BoundModel {
+    fn new(model_id: u32) -> Self {
+        Self {
+            model_id,
+            rows: RefCell::new(Vec::new()),
+            notify: ModelNotify::default(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.rows.borrow().len()
+    }
+
+    fn push(&self, value: Value) {
+        self.extend(vec![value]);
+    }
+
+    fn extend(&self, values: Vec<Value>) {
+        let count = values.len();
+        if count == 0 {
+            return;
+        }
+        let index = {
+            let mut rows = self.rows.borrow_mut();
+            let index = rows.len();
+            rows.extend(values);
+            index
+        };
+        self.notify.row_added(index, count);
+    }
+
+    fn insert(&self, index: usize, value: Value) {
+        self.rows.borrow_mut().insert(index, value);
+        self.notify.row_added(index, 1);
+    }
+
+    fn set(&self, index: usize, value: Value) {
+        self.rows.borrow_mut()[index] = value;
+        self.notify.row_changed(index);
+    }
+
+    fn remove_range(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.rows.borrow_mut().drain(index..index + count);
+        self.notify.row_removed(index, count);
+    }
+
+    /// Replaces every row, so views rebuild once instead of per row.
+    fn replace(&self, values: Vec<Value>) {
+        *self.rows.borrow_mut() = values;
+        self.notify.reset();
+    }
+
+    /// Swaps two rows. Views see both rows changed rather than a reset, so
+    /// the other rows keep their items.
+    fn swap(&self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        self.rows.borrow_mut().swap(a, b);
+        self.notify.row_changed(a);
+        self.notify.row_changed(b);
+    }
+
+    /// Moves the row at `from` so that it ends up at `to`. Views see the row
+    /// removed and inserted again, so the rows in between keep their items.
+    fn move_row(&self, from: usize, to: usize) {
+        if from == to {
+            return;
+        }
+        {
+            let mut rows = self.rows.borrow_mut();
+            if from < to {
+                rows[from..=to].rotate_left(1);
+            } else {
+                rows[to..=from].rotate_right(1);
+            }
+        }
+        self.notify.row_removed(from, 1);
+        self.notify.row_added(to, 1);
+    }
+}
+
+impl Model for BoundModel {
+    type Data = Value;
+
+    fn row_count(&self) -> usize {
+        self.len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Value> {
+        self.rows.borrow().get(row).cloned()
+    }
+
+    fn set_row_data(&self, row: usize, data: Value) {
+        if row >= self.len() {
+            return;
+        }
+        self.set(row, data.clone());
+        model_changed(self.model_id, row, data);
+    }
 
-    instance
-        .set_property(property_name, Value::Model(model_rc))
-        .map_err(|e| format!("Failed to bind model to property: {:?}", e))?;
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.notify
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
 
+fn model_changed(model_id: u32, row: usize, data: Value) {
+    let on_change = MODELS.with(|models| {
+        models
+            .borrow()
+            .get(&model_id)
+            .and_then(|handle| handle.on_change.clone())
+    });
+    if let Some((vm, handler)) = on_change {
+        run_ring_handler(
+            vm,
+            &handler,
+            &[Value::Number(row as f64), data],
+            &LangType::Void,
+        );
+    }
+}
+
+/// Allocates a model, lets `bind` put it where Slint reads it and registers
+/// it. `name` describes the binding in error messages.
+fn register_model(
+    name: String,
+    row_type: Option<LangType>,
+    bind: impl FnOnce(ModelRc<Value>) -> Result<(), String>,
+) -> Result<u32, String> {
     let id = NEXT_MODEL_ID.with(|next| {
         let id = *next.borrow();
         *next.borrow_mut() = id + 1;
         id
     });
 
+    let model = Rc::new(BoundModel::new(id));
+    bind(model.clone().into())?;
+
     MODELS.with(|models| {
         models.borrow_mut().insert(
             id,
             ModelHandle {
                 model,
-                property_name: property_name.to_string(),
+                property_name: name,
+                row_type,
+                on_change: None,
+                parent: None,
             },
         );
     });
@@ -44,127 +208,391 @@ pub fn model_create(instance: &ComponentInstance, property_name: &str) -> Result
     Ok(id)
 }
 
-pub fn model_push(model_id: u32, value: Value) -> Result<(), String> {
-    MODELS.with(|models| {
-        if let Some(handle) = models.borrow().get(&model_id) {
-            handle.model.push(value);
-            Ok(())
-        } else {
-            Err(format!("Model {} not found (property: unknown)", model_id))
-        }
+fn element_type(ty: Option<LangType>) -> Option<LangType> {
+    match ty {
+        Some(LangType::Array(row_type)) => Some(row_type.as_ref().clone()),
+        _ => None,
+    }
+}
+
+pub fn model_create(instance: &ComponentInstance, property_name: &str) -> Result<u32, String> {
+    let row_type = element_type(property_type(&instance.definition(), None, property_name));
+    register_model(property_name.to_string(), row_type, |model_rc| {
+        instance
+            .set_property(property_name, Value::Model(model_rc))
+            .map_err(|e| format!("Failed to bind model to property: {:?}", e))
     })
 }
 
-pub fn model_remove(model_id: u32, index: usize) -> Result<(), String> {
-    MODELS.with(|models| {
-        if let Some(handle) = models.borrow().get(&model_id) {
-            if index < handle.model.row_count() {
-                handle.model.remove(index);
-                Ok(())
-            } else {
-                Err(format!(
-                    "Index {} out of bounds for model '{}' (size: {})",
-                    index,
-                    handle.property_name,
-                    handle.model.row_count()
-                ))
-            }
-        } else {
-            Err(format!("Model {} not found", model_id))
-        }
+/// Takes over the model a property already holds, e.g. rows initialised in
+/// the .slint file: a model created by `model_create` keeps its ID, and any
+/// other model has its rows copied into a new managed model bound in its place.
+pub fn model_adopt(instance: &ComponentInstance, property_name: &str) -> Result<u32, String> {
+    let current = model_property(instance, property_name)?;
+    let managed = current
+        .as_any()
+        .downcast_ref::<BoundModel>()
+        .map(|bound| bound.model_id)
+        .filter(|id| MODELS.with(|models| models.borrow().contains_key(id)));
+    if let Some(id) = managed {
+        return Ok(id);
+    }
+
+    let rows: Vec<Value> = (0..current.row_count())
+        .filter_map(|row| current.row_data(row))
+        .collect();
+    let id = model_create(instance, property_name)?;
+    model_push_many(id, rows)?;
+    Ok(id)
+}
+
+/// The model a property holds, to read all its rows at once.
+pub fn model_property(
+    instance: &ComponentInstance,
+    property_name: &str,
+) -> Result<ModelRc<Value>, String> {
+    match instance.get_property(property_name) {
+        Ok(Value::Model(model)) => Ok(model),
+        Ok(_) => Err(format!("Property '{}' is not a model", property_name)),
+        Err(e) => Err(format!(
+            "Failed to get property '{}': {:?}",
+            property_name, e
+        )),
+    }
+}
+
+/// Binds a new model to a property of a global singleton.
+pub fn model_create_global(
+    instance: &ComponentInstance,
+    global: &str,
+    property_name: &str,
+) -> Result<u32, String> {
+    let row_type = element_type(property_type(
+        &instance.definition(),
+        Some(global),
+        property_name,
+    ));
+    let name = format!("{}.{}", global, property_name);
+    register_model(name, row_type, |model_rc| {
+        instance
+            .set_global_property(global, property_name, Value::Model(model_rc))
+            .map_err(|e| format!("Failed to bind model to global property: {:?}", e))
     })
 }
 
-pub fn model_set(model_id: u32, index: usize, value: Value) -> Result<(), String> {
+/// Binds a new model to an array field of a struct row of another model,
+/// e.g. the tasks of one project, so Ring can change that list without
+/// replacing the whole row.
+pub fn model_create_nested(model_id: u32, index: usize, field: &str) -> Result<u32, String> {
+    let (parent, parent_name) = bound_model(model_id)?;
+    let parent_row_type = model_row_type(model_id)?;
+
+    let row = parent
+        .row_data(index)
+        .ok_or_else(|| index_error(index, &parent_name, parent.row_count()))?;
+    let Value::Struct(mut row) = row else {
+        return Err(format!(
+            "Row {} of model '{}' is not a struct",
+            index, parent_name
+        ));
+    };
+
+    // The name as the row spells it, so the field is replaced rather than added.
+    let field_name = row
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .find(|name| same_identifier(name, field))
+        .ok_or_else(|| {
+            format!(
+                "Row {} of model '{}' has no field '{}'",
+                index, parent_name, field
+            )
+        })?;
+    let field_type = match &parent_row_type {
+        Some(LangType::Struct(s)) => s
+            .fields
+            .iter()
+            .find(|(name, _)| same_identifier(name, field))
+            .map(|(_, ty)| ty.clone()),
+        _ => None,
+    };
+    if let Some(ty) = field_type
+        .as_ref()
+        .filter(|ty| !matches!(ty, LangType::Array(_)))
+    {
+        return Err(format!(
+            "Field '{}' of model '{}' is {}, not a model",
+            field, parent_name, ty
+        ));
+    }
+
+    // A model created for the same field before is no longer in the row.
+    drop_nested(|nested| {
+        !(nested.model_id == model_id && nested.row == index && nested.field == field_name)
+    });
+    let name = format!("{}[{}].{}", parent_name, index, field_name);
+    let id = register_model(name, element_type(field_type), |model_rc| {
+        row.set_field(field_name.clone(), Value::Model(model_rc));
+        parent.set(index, Value::Struct(row));
+        Ok(())
+    })?;
     MODELS.with(|models| {
-        if let Some(handle) = models.borrow().get(&model_id) {
-            if index < handle.model.row_count() {
-                handle.model.set_row_data(index, value);
-                Ok(())
-            } else {
-                Err(format!(
-                    "Index {} out of bounds for model '{}' (size: {})",
-                    index,
-                    handle.property_name,
-                    handle.model.row_count()
-                ))
+        if let Some(handle) = models.borrow_mut().get_mut(&id) {
+            handle.parent = Some(ParentRow {
+                model_id,
+                row: index,
+                field: field_name,
+            });
+        }
+    });
+    Ok(id)
+}
+
+/// Destroys the nested models `keep` returns false for, and the models nested
+/// in those. `keep` may update the row of the ones it keeps.
+fn drop_nested(mut keep: impl FnMut(&mut ParentRow) -> bool) {
+    let stale: Vec<u32> = MODELS.with(|models| {
+        models
+            .borrow_mut()
+            .iter_mut()
+            .filter_map(|(&id, handle)| {
+                let parent = handle.parent.as_mut()?;
+                (!keep(parent)).then_some(id)
+            })
+            .collect()
+    });
+    for id in stale {
+        let _ = model_destroy(id);
+    }
+}
+
+/// Moves the models nested in `model_id` along with their rows: `row_map`
+/// gives a row's new index, or None when the row was removed or replaced.
+fn remap_nested(model_id: u32, row_map: impl Fn(usize) -> Option<usize>) {
+    drop_nested(|nested| {
+        if nested.model_id != model_id {
+            return true;
+        }
+        match row_map(nested.row) {
+            Some(row) => {
+                nested.row = row;
+                true
             }
-        } else {
-            Err(format!("Model {} not found", model_id))
+            None => false,
         }
-    })
+    });
 }
 
-pub fn model_count(model_id: u32) -> Result<usize, String> {
+/// The model and the name of its binding. The registry is not borrowed while
+/// the model notifies its views, which may run Ring code that creates or
+/// destroys models.
+fn bound_model(model_id: u32) -> Result<(Rc<BoundModel>, String), String> {
     MODELS.with(|models| {
-        if let Some(handle) = models.borrow().get(&model_id) {
-            Ok(handle.model.row_count())
-        } else {
-            Err(format!("Model {} not found", model_id))
-        }
+        models
+            .borrow()
+            .get(&model_id)
+            .map(|handle| (handle.model.clone(), handle.property_name.clone()))
+            .ok_or_else(|| format!("Model {} not found", model_id))
     })
 }
 
+fn index_error(index: usize, name: &str, size: usize) -> String {
+    format!(
+        "Index {} out of bounds for model '{}' (size: {})",
+        index, name, size
+    )
+}
+
+pub fn model_push(model_id: u32, value: Value) -> Result<(), String> {
+    let (model, _) = bound_model(model_id)?;
+    model.push(value);
+    Ok(())
+}
+
+pub fn model_remove(model_id: u32, index: usize) -> Result<(), String> {
+    let (model, name) = bound_model(model_id)?;
+    let size = model.row_count();
+    if index >= size {
+        return Err(index_error(index, &name, size));
+    }
+    model.remove_range(index, 1);
+    remap_nested(model_id, |row| removed_row(row, index, 1));
+    Ok(())
+}
+
+/// Where a row ends up after `count` rows from `index` were removed.
+fn removed_row(row: usize, index: usize, count: usize) -> Option<usize> {
+    if row < index {
+        Some(row)
+    } else if row < index + count {
+        None
+    } else {
+        Some(row - count)
+    }
+}
+
+pub fn model_set(model_id: u32, index: usize, value: Value) -> Result<(), String> {
+    let (model, name) = bound_model(model_id)?;
+    let size = model.row_count();
+    if index >= size {
+        return Err(index_error(index, &name, size));
+    }
+    model.set(index, value);
+    remap_nested(model_id, |row| (row != index).then_some(row));
+    Ok(())
+}
+
+pub fn model_count(model_id: u32) -> Result<usize, String> {
+    let (model, _) = bound_model(model_id)?;
+    Ok(model.row_count())
+}
+
 pub fn model_clear(model_id: u32) -> Result<(), String> {
-    MODELS.with(|models| {
-        if let Some(handle) = models.borrow().get(&model_id) {
-            while handle.model.row_count() > 0 {
-                handle.model.remove(handle.model.row_count() - 1);
-            }
-            Ok(())
-        } else {
-            Err(format!("Model {} not found", model_id))
-        }
-    })
+    let (model, _) = bound_model(model_id)?;
+    model.replace(Vec::new());
+    remap_nested(model_id, |_| None);
+    Ok(())
 }
 
 pub fn model_insert(model_id: u32, index: usize, value: Value) -> Result<(), String> {
-    MODELS.with(|models| {
-        if let Some(handle) = models.borrow().get(&model_id) {
-            if index <= handle.model.row_count() {
-                handle.model.insert(index, value);
-                Ok(())
-            } else {
-                Err(format!(
-                    "Index {} out of bounds for insert in model '{}' (size: {})",
-                    index,
-                    handle.property_name,
-                    handle.model.row_count()
-                ))
-            }
-        } else {
-            Err(format!("Model {} not found", model_id))
-        }
-    })
+    let (model, name) = bound_model(model_id)?;
+    let size = model.row_count();
+    if index > size {
+        return Err(format!(
+            "Index {} out of bounds for insert in model '{}' (size: {})",
+            index, name, size
+        ));
+    }
+    model.insert(index, value);
+    remap_nested(model_id, |row| {
+        Some(if row >= index { row + 1 } else { row })
+    });
+    Ok(())
+}
+
+pub fn model_push_many(model_id: u32, values: Vec<Value>) -> Result<(), String> {
+    let (model, _) = bound_model(model_id)?;
+    model.extend(values);
+    Ok(())
+}
+
+pub fn model_replace(model_id: u32, values: Vec<Value>) -> Result<(), String> {
+    let (model, _) = bound_model(model_id)?;
+    model.replace(values);
+    remap_nested(model_id, |_| None);
+    Ok(())
+}
+
+pub fn model_remove_range(model_id: u32, index: usize, count: usize) -> Result<(), String> {
+    let (model, name) = bound_model(model_id)?;
+    let size = model.row_count();
+    if !index.checked_add(count).is_some_and(|end| end <= size) {
+        return Err(format!(
+            "Range {}..{} out of bounds for model '{}' (size: {})",
+            index,
+            index.saturating_add(count),
+            name,
+            size
+        ));
+    }
+    model.remove_range(index, count);
+    remap_nested(model_id, |row| removed_row(row, index, count));
+    Ok(())
+}
+
+pub fn model_swap(model_id: u32, a: usize, b: usize) -> Result<(), String> {
+    let (model, name) = bound_model(model_id)?;
+    let size = model.row_count();
+    if let Some(index) = [a, b].into_iter().find(|&index| index >= size) {
+        return Err(index_error(index, &name, size));
+    }
+    model.swap(a, b);
+    remap_nested(model_id, |row| {
+        Some(match row {
+            row if row == a => b,
+            row if row == b => a,
+            row => row,
+        })
+    });
+    Ok(())
+}
+
+pub fn model_move(model_id: u32, from: usize, to: usize) -> Result<(), String> {
+    let (model, name) = bound_model(model_id)?;
+    let size = model.row_count();
+    if let Some(index) = [from, to].into_iter().find(|&index| index >= size) {
+        return Err(index_error(index, &name, size));
+    }
+    model.move_row(from, to);
+    remap_nested(model_id, |row| {
+        Some(match row {
+            row if row == from => to,
+            row if from < to && (from..=to).contains(&row) => row - 1,
+            row if to < from && (to..from).contains(&row) => row + 1,
+            row => row,
+        })
+    });
+    Ok(())
 }
 
 pub fn model_destroy(model_id: u32) -> Result<(), String> {
-    MODELS.with(|models| {
-        if models.borrow_mut().remove(&model_id).is_some() {
+    let removed = MODELS.with(|models| models.borrow_mut().remove(&model_id));
+    match removed {
+        Some(handle) => {
+            if let Some((vm, handler)) = handle.on_change {
+                handler.release(vm);
+            }
+            remap_nested(model_id, |_| None);
             Ok(())
-        } else {
-            Err(format!("Model {} not found", model_id))
         }
-    })
+        None => Err(format!("Model {} not found", model_id)),
+    }
+}
+
+/// Sets the handler called when the UI edits a row, or removes it when
+/// `handler` is None.
+pub fn model_on_change(model_id: u32, vm: RingVM, handler: Option<&str>) -> Result<(), String> {
+    let handler = handler.map(RingHandler::new);
+    let replaced = MODELS.with(|models| {
+        let mut models = models.borrow_mut();
+        let handle = models
+            .get_mut(&model_id)
+            .ok_or_else(|| format!("Model {} not found", model_id))?;
+        let on_change = handler.clone().map(|handler| (vm, handler));
+        Ok::<_, String>(std::mem::replace(&mut handle.on_change, on_change))
+    })?;
+    match replaced {
+        Some((vm, old)) if handler.as_ref().map(RingHandler::as_str) != Some(old.as_str()) => {
+            old.release(vm)
+        }
+        _ => {}
+    }
+    Ok(())
 }
 
 pub fn model_get(model_id: u32, index: usize) -> Result<Value, String> {
+    let (model, name) = bound_model(model_id)?;
+    model
+        .row_data(index)
+        .ok_or_else(|| index_error(index, &name, model.row_count()))
+}
+
+/// The rows of a model as Slint sees them, e.g. to build views on top.
+pub fn model_rc(model_id: u32) -> Result<ModelRc<Value>, String> {
     MODELS.with(|models| {
-        if let Some(handle) = models.borrow().get(&model_id) {
-            handle.model.row_data(index).ok_or_else(|| {
-                format!(
-                    "Index {} out of bounds for model '{}' (size: {})",
-                    index,
-                    handle.property_name,
-                    handle.model.row_count()
-                )
-            })
-        } else {
-            Err(format!("Model {} not found", model_id))
-        }
+        models
+            .borrow()
+            .get(&model_id)
+            .map(|handle| handle.model.clone().into())
+            .ok_or_else(|| format!("Model {} not found", model_id))
     })
 }
 
+/// The whole model as a value, sharing its rows.
+pub fn model_value(model_id: u32) -> Result<Value, String> {
+    model_rc(model_id).map(Value::Model)
+}
+
 pub fn ring_param_to_model_value(p: *mut libc::c_void, param: i32) -> Value {
     if ring_api_isnumber(p, param) {
         Value::Number(ring_api_getnumber(p, param))
@@ -178,3 +606,33 @@ pub fn ring_param_to_model_value(p: *mut libc::c_void, param: i32) -> Value {
         Value::Void
     }
 }
+
+/// Converts a Ring parameter to a row of the model, using the declared
+/// element type of its property when it is known.
+pub fn ring_param_to_row_value(
+    p: *mut libc::c_void,
+    param: i32,
+    model_id: u32,
+) -> Result<Value, String> {
+    match model_row_type(model_id)? {
+        Some(row_type) => ring_param_to_value(p, param, Some(&row_type))
+            .map_err(|e| format!("Invalid row for model {}: {}", model_id, e)),
+        None => Ok(ring_param_to_model_value(p, param)),
+    }
+}
+
+/// Converts every item of a Ring list to a row of the model.
+pub fn ring_list_to_model_rows(list: RingList, model_id: u32) -> Result<Vec<Value>, String> {
+    ring_list_to_rows(list, model_row_type(model_id)?.as_ref())
+        .map_err(|e| format!("Invalid rows for model {}: {}", model_id, e))
+}
+
+fn model_row_type(model_id: u32) -> Result<Option<LangType>, String> {
+    MODELS.with(|models| {
+        models
+            .borrow()
+            .get(&model_id)
+            .map(|handle| handle.row_type.clone())
+            .ok_or_else(|| format!("Model {} not found", model_id))
+    })
+}
@@ -0,0 +1,471 @@
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::model::{FilterModel, Model, ModelRc, SortModel};
+use ring_lang_rs::RingVM;
+use slint_interpreter::{ComponentInstance, Value};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::slint::{
+    RingHandler, format_number, model_rc, run_ring_handler, same_identifier, value_to_json_string,
+};
+
+/// Sort keys a view keeps before it forgets them and asks Ring again.
+const MAX_SORT_KEYS: usize = 65536;
+
+thread_local! {
+    static VIEWS: RefCell<HashMap<u32, Rc<ModelView>>> = RefCell::new(HashMap::new());
+    static NEXT_VIEW_ID: RefCell<u32> = const { RefCell::new(1) };
+}
+
+type RowFilter = Box<dyn Fn(&Value) -> bool>;
+type RowOrder = Box<dyn FnMut(&Value, &Value) -> Ordering>;
+type FilteredRows = FilterModel<ModelRc<Value>, RowFilter>;
+type SortedRows = SortModel<Rc<FilteredRows>, RowOrder>;
+
+/// A sorted and filtered view of a model, bound to its own property. Slint's
+/// adapters follow changes to the source rows by themselves; changing the
+/// settings re-applies them.
+struct ModelView {
+    property_name: String,
+    settings: Rc<RefCell<ViewSettings>>,
+    filtered: Rc<FilteredRows>,
+    sorted: Rc<SortedRows>,
+}
+
+/// Held behind `Rc`s so the adapters can take a copy and drop the borrow
+/// before running Ring code, which may change the settings again.
+#[derive(Default)]
+struct ViewSettings {
+    sort: Option<Rc<ViewSort>>,
+    filter: Option<Rc<ViewFilter>>,
+    search: Option<Rc<ViewSearch>>,
+}
+
+enum ViewSort {
+    Field {
+        name: String,
+        descending: bool,
+    },
+    /// Sorts by the key a Ring function gives each row. Sorting compares rows
+    /// many times, so the keys are kept by row content and Ring is asked once
+    /// per distinct row.
+    Ring {
+        vm: RingVM,
+        handler: RingHandler,
+        keys: RefCell<HashMap<String, Value>>,
+    },
+}
+
+enum ViewFilter {
+    Field {
+        name: String,
+        op: FilterOp,
+        value: Value,
+    },
+    Ring {
+        vm: RingVM,
+        handler: RingHandler,
+    },
+}
+
+/// Lowercased text looked for in `fields`, or in every field when empty.
+struct ViewSearch {
+    text: String,
+    fields: Vec<String>,
+}
+
+#[derive(Clone, Copy)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+impl FilterOp {
+    fn parse(op: &str) -> Result<Self, String> {
+        match op.trim().to_lowercase().as_str() {
+            "=" | "==" => Ok(Self::Eq),
+            "!=" | "<>" => Ok(Self::Ne),
+            "<" => Ok(Self::Lt),
+            "<=" => Ok(Self::Le),
+            ">" => Ok(Self::Gt),
+            ">=" => Ok(Self::Ge),
+            "contains" => Ok(Self::Contains),
+            other => Err(format!(
+                "Unknown filter operator '{}' (expected =, !=, <, <=, >, >= or contains)",
+                other
+            )),
+        }
+    }
+
+    fn test(self, field: &Value, value: &Value) -> bool {
+        let ordering = || compare_values(field, &coerce_like(value, field));
+        match self {
+            Self::Eq => ordering() == Ordering::Equal,
+            Self::Ne => ordering() != Ordering::Equal,
+            Self::Lt => ordering() == Ordering::Less,
+            Self::Le => ordering() != Ordering::Greater,
+            Self::Gt => ordering() == Ordering::Greater,
+            Self::Ge => ordering() != Ordering::Less,
+            Self::Contains => value_text(field)
+                .zip(value_text(value))
+                .is_some_and(|(field, value)| field.to_lowercase().contains(&value.to_lowercase())),
+        }
+    }
+}
+
+impl ViewSort {
+    fn compare(&self, a: &Value, b: &Value) -> Ordering {
+        match self {
+            Self::Field { name, descending } => {
+                let ordering = compare_values(&field_value(a, name), &field_value(b, name));
+                if *descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+            Self::Ring { .. } => compare_values(&self.ring_key(a), &self.ring_key(b)),
+        }
+    }
+
+    fn ring_key(&self, row: &Value) -> Value {
+        let Self::Ring { vm, handler, keys } = self else {
+            return Value::Void;
+        };
+        let id = value_to_json_string(row);
+        if let Some(key) = keys.borrow().get(&id).cloned() {
+            return key;
+        }
+        // Not borrowed while Ring runs. An invalid type leaves the key's type
+        // to the value Ring returns.
+        let key = run_ring_handler(*vm, handler, std::slice::from_ref(row), &LangType::Invalid);
+        let mut keys = keys.borrow_mut();
+        if keys.len() >= MAX_SORT_KEYS {
+            keys.clear();
+        }
+        keys.insert(id, key.clone());
+        key
+    }
+}
+
+impl ViewFilter {
+    fn matches(&self, row: &Value) -> bool {
+        match self {
+            Self::Field { name, op, value } => op.test(&field_value(row, name), value),
+            Self::Ring { vm, handler } => matches!(
+                run_ring_handler(*vm, handler, std::slice::from_ref(row), &LangType::Bool),
+                Value::Bool(true)
+            ),
+        }
+    }
+}
+
+impl ViewSearch {
+    fn matches(&self, row: &Value) -> bool {
+        let contains = |value: &Value| {
+            value_text(value).is_some_and(|t| t.to_lowercase().contains(&self.text))
+        };
+        match row {
+            Value::Struct(s) if self.fields.is_empty() => {
+                s.iter().any(|(_, value)| contains(value))
+            }
+            _ if self.fields.is_empty() => contains(row),
+            _ => self
+                .fields
+                .iter()
+                .any(|name| contains(&field_value(row, name))),
+        }
+    }
+}
+
+impl ViewSettings {
+    fn ring_handlers(&self) -> Vec<(RingVM, RingHandler)> {
+        let sort = self.sort.as_deref().and_then(|sort| match sort {
+            ViewSort::Ring { vm, handler, .. } => Some((*vm, handler.clone())),
+            ViewSort::Field { .. } => None,
+        });
+        let filter = self.filter.as_deref().and_then(|filter| match filter {
+            ViewFilter::Ring { vm, handler } => Some((*vm, handler.clone())),
+            ViewFilter::Field { .. } => None,
+        });
+        sort.into_iter().chain(filter).collect()
+    }
+}
+
+/// A field of a struct row, or the row itself when `name` is empty.
+fn field_value(row: &Value, name: &str) -> Value {
+    if name.is_empty() {
+        return row.clone();
+    }
+    match row {
+        Value::Struct(s) => s
+            .iter()
+            .find(|(key, _)| same_identifier(key, name))
+            .map(|(_, value)| value.clone())
+            .unwrap_or(Value::Void),
+        _ => Value::Void,
+    }
+}
+
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.to_string()),
+        Value::Number(n) => Some(format_number(*n)),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::EnumerationValue(_, variant) => Some(variant.clone()),
+        _ => None,
+    }
+}
+
+/// Converts a filter value given from Ring to the kind of value in the field,
+/// so `"5"` matches a number field and `1` a bool one.
+fn coerce_like(value: &Value, like: &Value) -> Value {
+    match (like, value) {
+        (Value::Number(_), Value::String(s)) => s
+            .trim()
+            .parse()
+            .map_or_else(|_| value.clone(), Value::Number),
+        (Value::Bool(_), Value::Number(n)) => Value::Bool(*n != 0.0),
+        (Value::String(_), Value::Number(n)) => Value::String(format_number(*n).into()),
+        (Value::EnumerationValue(e, _), Value::String(s)) => {
+            Value::EnumerationValue(e.clone(), s.to_string())
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Orders values of the same kind naturally (text case-insensitively, lists
+/// item by item), and values of different kinds by kind, empty ones first.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Void => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::EnumerationValue(..) => 4,
+            Value::Model(_) => 5,
+            _ => 6,
+        }
+    }
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::String(a), Value::String(b)) => a
+            .to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b)),
+        (Value::EnumerationValue(_, a), Value::EnumerationValue(_, b)) => a.cmp(b),
+        (Value::Model(a), Value::Model(b)) => a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| compare_values(&a, &b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.row_count().cmp(&b.row_count())),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn view(view_id: u32) -> Result<Rc<ModelView>, String> {
+    VIEWS.with(|views| {
+        views
+            .borrow()
+            .get(&view_id)
+            .cloned()
+            .ok_or_else(|| format!("Model view {} not found", view_id))
+    })
+}
+
+/// Applies `update` to the view's settings and lets go of the Ring handlers
+/// it replaced.
+fn update_settings(view: &ModelView, update: impl FnOnce(&mut ViewSettings)) {
+    let before = view.settings.borrow().ring_handlers();
+    update(&mut view.settings.borrow_mut());
+    let after = view.settings.borrow().ring_handlers();
+    for (vm, handler) in before {
+        if !after
+            .iter()
+            .any(|(_, kept)| kept.as_str() == handler.as_str())
+        {
+            handler.release(vm);
+        }
+    }
+}
+
+pub fn model_view_create(
+    instance: &ComponentInstance,
+    model_id: u32,
+    property_name: &str,
+) -> Result<u32, String> {
+    let source = model_rc(model_id)?;
+    let settings = Rc::new(RefCell::new(ViewSettings::default()));
+
+    let filter_settings = settings.clone();
+    let filter: RowFilter = Box::new(move |row| {
+        let (filter, search) = {
+            let settings = filter_settings.borrow();
+            (settings.filter.clone(), settings.search.clone())
+        };
+        filter.is_none_or(|filter| filter.matches(row))
+            && search.is_none_or(|search| search.matches(row))
+    });
+    let filtered = Rc::new(FilterModel::new(source, filter));
+
+    let sort_settings = settings.clone();
+    let order: RowOrder = Box::new(move |a, b| {
+        let sort = sort_settings.borrow().sort.clone();
+        sort.map_or(Ordering::Equal, |sort| sort.compare(a, b))
+    });
+    let sorted = Rc::new(SortModel::new(filtered.clone(), order));
+
+    let model_rc: ModelRc<Value> = sorted.clone().into();
+    instance
+        .set_property(property_name, Value::Model(model_rc))
+        .map_err(|e| format!("Failed to bind model view to property: {:?}", e))?;
+
+    let id = NEXT_VIEW_ID.with(|next| {
+        let id = *next.borrow();
+        *next.borrow_mut() = id + 1;
+        id
+    });
+    VIEWS.with(|views| {
+        views.borrow_mut().insert(
+            id,
+            Rc::new(ModelView {
+                property_name: property_name.to_string(),
+                settings,
+                filtered,
+                sorted,
+            }),
+        );
+    });
+
+    Ok(id)
+}
+
+/// Sorts by a field of the rows, or by the rows themselves when `field` is empty.
+pub fn model_view_sort_by(view_id: u32, field: &str, descending: bool) -> Result<(), String> {
+    let view = view(view_id)?;
+    update_settings(&view, |settings| {
+        settings.sort = Some(Rc::new(ViewSort::Field {
+            name: field.to_string(),
+            descending,
+        }));
+    });
+    view.sorted.reset();
+    Ok(())
+}
+
+/// Sorts by the key a Ring function returns for each row (a number, a string
+/// or a list of them); None keeps the source order.
+pub fn model_view_sort_func(view_id: u32, vm: RingVM, handler: Option<&str>) -> Result<(), String> {
+    let view = view(view_id)?;
+    update_settings(&view, |settings| {
+        settings.sort = handler.map(|handler| {
+            Rc::new(ViewSort::Ring {
+                vm,
+                handler: RingHandler::new(handler),
+                keys: RefCell::new(HashMap::new()),
+            })
+        });
+    });
+    view.sorted.reset();
+    Ok(())
+}
+
+/// Keeps the rows whose `field` compares to `value` with `op`.
+pub fn model_view_filter(view_id: u32, field: &str, op: &str, value: Value) -> Result<(), String> {
+    let op = FilterOp::parse(op)?;
+    let view = view(view_id)?;
+    update_settings(&view, |settings| {
+        settings.filter = Some(Rc::new(ViewFilter::Field {
+            name: field.to_string(),
+            op,
+            value,
+        }));
+    });
+    view.filtered.reset();
+    Ok(())
+}
+
+/// Keeps the rows a Ring function returns true for; None removes the filter.
+pub fn model_view_filter_func(
+    view_id: u32,
+    vm: RingVM,
+    handler: Option<&str>,
+) -> Result<(), String> {
+    let view = view(view_id)?;
+    update_settings(&view, |settings| {
+        settings.filter = handler.map(|handler| {
+            Rc::new(ViewFilter::Ring {
+                vm,
+                handler: RingHandler::new(handler),
+            })
+        });
+    });
+    view.filtered.reset();
+    Ok(())
+}
+
+/// Keeps the rows containing `text`, ignoring case, in one of `fields` or in
+/// any field when `fields` is empty. Empty text shows every row again.
+pub fn model_view_search(view_id: u32, text: &str, fields: Vec<String>) -> Result<(), String> {
+    let view = view(view_id)?;
+    let text = text.trim().to_lowercase();
+    update_settings(&view, |settings| {
+        settings.search = (!text.is_empty()).then(|| Rc::new(ViewSearch { text, fields }));
+    });
+    view.filtered.reset();
+    Ok(())
+}
+
+/// Removes the filter and the search text.
+pub fn model_view_clear_filter(view_id: u32) -> Result<(), String> {
+    let view = view(view_id)?;
+    update_settings(&view, |settings| {
+        settings.filter = None;
+        settings.search = None;
+    });
+    view.filtered.reset();
+    Ok(())
+}
+
+pub fn model_view_count(view_id: u32) -> Result<usize, String> {
+    Ok(view(view_id)?.sorted.row_count())
+}
+
+/// Index in the source model of the row shown at `row`, e.g. to update the
+/// item the user selected with `modelSet()`.
+pub fn model_view_source_index(view_id: u32, row: usize) -> Result<usize, String> {
+    let view = view(view_id)?;
+    let count = view.sorted.row_count();
+    if row >= count {
+        return Err(format!(
+            "Index {} out of bounds for model view '{}' (size: {})",
+            row, view.property_name, count
+        ));
+    }
+    Ok(view.filtered.unfiltered_row(view.sorted.unsorted_row(row)))
+}
+
+pub fn model_view_destroy(view_id: u32) -> Result<(), String> {
+    let removed = VIEWS.with(|views| views.borrow_mut().remove(&view_id));
+    match removed {
+        Some(view) => {
+            // The property may still show the view, so it must not call
+            // Ring functions that are released.
+            update_settings(&view, |settings| *settings = ViewSettings::default());
+            view.filtered.reset();
+            view.sorted.reset();
+            Ok(())
+        }
+        None => Err(format!("Model view {} not found", view_id)),
+    }
+}
@@ -0,0 +1,219 @@
+use crate::slint::{
+    CompileDiagnostic, SlintInstanceWrapper, get_component_by_name, last_diagnostics,
+    last_loaded_files, loaded_files_of, report_diagnostic, set_callback, set_global_callback,
+    watch_restart, window_get_position, window_get_size, window_set_position, window_set_size,
+};
+use i_slint_core::timers::{Timer, TimerMode};
+use ring_lang_rs::RingVM;
+use slint_interpreter::ComponentHandle;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+thread_local! {
+    static RELOADERS: RefCell<HashMap<u32, ReloadHandle>> = RefCell::new(HashMap::new());
+    static NEXT_RELOAD_ID: RefCell<u32> = const { RefCell::new(1) };
+}
+
+struct ReloadHandle {
+    /// Owned by Ring. It is only freed by `free_slint_instance`, which calls
+    /// `hot_reload_forget` first, so it is valid for as long as the handle is
+    /// registered.
+    wrapper: *mut SlintInstanceWrapper,
+    path: String,
+    component_name: String,
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    /// What the last reload reported, kept apart from `last_diagnostics()`
+    /// which the next build replaces.
+    diagnostics: Vec<CompileDiagnostic>,
+    _timer: Timer,
+}
+
+fn snapshot_files(files: Vec<PathBuf>) -> Vec<(PathBuf, Option<SystemTime>)> {
+    files
+        .into_iter()
+        .map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn files_changed(files: &[(PathBuf, Option<SystemTime>)]) -> bool {
+    files.iter().any(|(path, modified)| {
+        let current = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        current != *modified
+    })
+}
+
+/// Watches `path` (and every file it imports) and swaps the instance held by
+/// `wrapper` for a freshly compiled one whenever one of them changes.
+pub fn hot_reload_start(
+    wrapper: *mut SlintInstanceWrapper,
+    path: &str,
+    interval_ms: u64,
+) -> Result<u32, String> {
+    let component_name = unsafe { (*wrapper).instance.definition().name().to_string() };
+    // The files of the build the window came from, so that starting does not
+    // build again and replace the diagnostics Ring may still read.
+    let files = loaded_files_of(path)
+        .map(snapshot_files)
+        .ok_or_else(|| format!("'{}' was not loaded from a file", path))?;
+
+    let id = NEXT_RELOAD_ID.with(|next| {
+        let id = *next.borrow();
+        *next.borrow_mut() = id + 1;
+        id
+    });
+
+    let timer = Timer::default();
+    timer.start(
+        TimerMode::Repeated,
+        Duration::from_millis(interval_ms),
+        move || hot_reload_check(id),
+    );
+
+    RELOADERS.with(|reloaders| {
+        reloaders.borrow_mut().insert(
+            id,
+            ReloadHandle {
+                wrapper,
+                path: path.to_string(),
+                component_name,
+                files,
+                diagnostics: Vec::new(),
+                _timer: timer,
+            },
+        );
+    });
+
+    Ok(id)
+}
+
+pub fn hot_reload_stop(reload_id: u32) -> Result<(), String> {
+    RELOADERS.with(|reloaders| {
+        if reloaders.borrow_mut().remove(&reload_id).is_some() {
+            Ok(())
+        } else {
+            Err(format!("Hot reload {} not found", reload_id))
+        }
+    })
+}
+
+/// Diagnostics of the last reload: compiler messages, or why the window could
+/// not be recreated. Empty until a file changes.
+pub fn hot_reload_diagnostics(reload_id: u32) -> Result<Vec<CompileDiagnostic>, String> {
+    RELOADERS.with(|reloaders| {
+        reloaders
+            .borrow()
+            .get(&reload_id)
+            .map(|handle| handle.diagnostics.clone())
+            .ok_or_else(|| format!("Hot reload {} not found", reload_id))
+    })
+}
+
+/// Drops every watcher of an instance that is about to be freed.
+pub fn hot_reload_forget(wrapper: *mut SlintInstanceWrapper) {
+    RELOADERS.with(|reloaders| {
+        reloaders.borrow_mut().retain(|_, h| h.wrapper != wrapper);
+    });
+}
+
+fn hot_reload_check(reload_id: u32) {
+    let pending = RELOADERS.with(|reloaders| {
+        let reloaders = reloaders.borrow();
+        let handle = reloaders.get(&reload_id)?;
+        if !files_changed(&handle.files) {
+            return None;
+        }
+        Some((
+            handle.wrapper,
+            handle.path.clone(),
+            handle.component_name.clone(),
+        ))
+    });
+
+    let Some((wrapper, path, component_name)) = pending else {
+        return;
+    };
+
+    let result = get_component_by_name(&path, &component_name);
+    let files = snapshot_files(last_loaded_files());
+    RELOADERS.with(|reloaders| {
+        if let Some(handle) = reloaders.borrow_mut().get_mut(&reload_id) {
+            handle.files = files;
+        }
+    });
+
+    let outcome = result.and_then(|component| {
+        // Still registered, so the wrapper has not been freed (see `ReloadHandle`).
+        let wrapper = unsafe { &mut *wrapper };
+        replace_instance(wrapper, &component.definition, &path)
+    });
+    if let Err(e) = outcome {
+        // Compiler errors are in the diagnostics already.
+        let compile_failed = last_diagnostics().iter().any(|d| d.severity == "error");
+        if !compile_failed {
+            report_diagnostic(&path, format!("Hot reload failed: {}", e));
+        }
+    }
+    let diagnostics = last_diagnostics();
+    RELOADERS.with(|reloaders| {
+        if let Some(handle) = reloaders.borrow_mut().get_mut(&reload_id) {
+            handle.diagnostics = diagnostics;
+        }
+    });
+}
+
+fn replace_instance(
+    wrapper: &mut SlintInstanceWrapper,
+    definition: &slint_interpreter::ComponentDefinition,
+    path: &str,
+) -> Result<(), String> {
+    let new_instance = definition
+        .create()
+        .map_err(|e| format!("Failed to create instance: {:?}", e))?;
+    let old_instance = &wrapper.instance;
+    let vm = wrapper.vm_ptr as RingVM;
+
+    {
+        let bindings = wrapper.bindings.borrow();
+        for name in &bindings.properties {
+            if let Ok(value) = old_instance.get_property(name) {
+                let _ = new_instance.set_property(name, value);
+            }
+        }
+        for (global, name) in &bindings.global_properties {
+            if let Ok(value) = old_instance.get_global_property(global, name) {
+                let _ = new_instance.set_global_property(global, name, value);
+            }
+        }
+        for (name, handler) in &bindings.callbacks {
+            let _ = set_callback(&new_instance, name, vm, handler.clone());
+        }
+        for (global, name, handler) in &bindings.global_callbacks {
+            let _ = set_global_callback(&new_instance, global, name, vm, handler.clone());
+        }
+    }
+
+    let (x, y) = window_get_position(old_instance);
+    let (width, height) = window_get_size(old_instance);
+    window_set_position(&new_instance, x, y);
+    window_set_size(&new_instance, width, height);
+
+    let visible = old_instance.window().is_visible();
+    if visible {
+        new_instance
+            .show()
+            .map_err(|e| format!("Failed to show window: {:?}", e))?;
+    }
+
+    let old_instance = std::mem::replace(&mut wrapper.instance, new_instance);
+    if visible {
+        let _ = old_instance.hide();
+    }
+    watch_restart(wrapper, path);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
+use crate::slint::RingHandler;
 use i_slint_core::timers::{Timer, TimerMode};
-use ring_lang_rs::{RingVM, ring_vm_runcode_str};
+use ring_lang_rs::RingVM;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::time::Duration;
@@ -11,6 +12,8 @@ thread_local! {
 
 struct TimerHandle {
     timer: Timer,
+    vm: RingVM,
+    handler: RingHandler,
 }
 
 pub fn timer_start(interval_ms: u64, repeated: bool, vm: RingVM, callback_name: String) -> u32 {
@@ -21,10 +24,10 @@ pub fn timer_start(interval_ms: u64, repeated: bool, vm: RingVM, callback_name:
         TimerMode::SingleShot
     };
 
+    let handler = RingHandler::new(&callback_name);
+    let timer_handler = handler.clone();
     timer.start(mode, Duration::from_millis(interval_ms), move || {
-        let func_name = callback_name.to_lowercase();
-        let code = format!("{}()", func_name);
-        ring_vm_runcode_str(vm, &code);
+        timer_handler.call(vm);
     });
 
     let id = NEXT_TIMER_ID.with(|next| {
@@ -34,21 +37,23 @@ pub fn timer_start(interval_ms: u64, repeated: bool, vm: RingVM, callback_name:
     });
 
     TIMERS.with(|timers| {
-        timers.borrow_mut().insert(id, TimerHandle { timer });
+        timers
+            .borrow_mut()
+            .insert(id, TimerHandle { timer, vm, handler });
     });
 
     id
 }
 
 pub fn timer_stop(timer_id: u32) -> Result<(), String> {
-    TIMERS.with(|timers| {
-        if let Some(handle) = timers.borrow_mut().remove(&timer_id) {
-            handle.timer.stop();
-            Ok(())
-        } else {
-            Err(format!("Timer {} not found", timer_id))
-        }
-    })
+    let handle = TIMERS.with(|timers| timers.borrow_mut().remove(&timer_id));
+    if let Some(handle) = handle {
+        handle.timer.stop();
+        handle.handler.release(handle.vm);
+        Ok(())
+    } else {
+        Err(format!("Timer {} not found", timer_id))
+    }
 }
 
 pub fn timer_running(timer_id: u32) -> Result<bool, String> {
@@ -1,4 +1,5 @@
-use ring_lang_rs::{RingVM, ring_vm_runcode_str};
+use crate::slint::RingHandler;
+use ring_lang_rs::RingVM;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -7,7 +8,21 @@ static NEXT_MENU_ID: AtomicU32 = AtomicU32::new(1);
 
 thread_local! {
     static TRAY_VM: RefCell<Option<RingVM>> = const { RefCell::new(None) };
-    static MENU_CALLBACKS: RefCell<HashMap<u32, String>> = RefCell::new(HashMap::new());
+    static MENU_CALLBACKS: RefCell<HashMap<u32, RingHandler>> = RefCell::new(HashMap::new());
+}
+
+/// Drops every menu callback, letting go of objects bound with slintMethod().
+#[cfg(not(target_os = "android"))]
+fn release_menu_callbacks() {
+    let handlers: Vec<RingHandler> =
+        MENU_CALLBACKS.with(|c| c.borrow_mut().drain().map(|(_, h)| h).collect());
+    TRAY_VM.with(|v| {
+        if let Some(vm) = *v.borrow() {
+            for handler in &handlers {
+                handler.release(vm);
+            }
+        }
+    });
 }
 
 // =============================================================================
@@ -73,7 +88,7 @@ mod platform {
     pub fn create(tooltip: &str) -> Result<(), String> {
         *TRAY_TOOLTIP.lock().unwrap() = tooltip.to_string();
         MENU_ITEMS.lock().unwrap().clear();
-        MENU_CALLBACKS.with(|c| c.borrow_mut().clear());
+        release_menu_callbacks();
 
         let tray = RingTray;
         let handle = tray
@@ -109,7 +124,7 @@ mod platform {
         let id = NEXT_MENU_ID.fetch_add(1, Ordering::SeqCst);
 
         MENU_ITEMS.lock().unwrap().push((id, label.to_string()));
-        MENU_CALLBACKS.with(|c| c.borrow_mut().insert(id, callback));
+        MENU_CALLBACKS.with(|c| c.borrow_mut().insert(id, RingHandler::new(&callback)));
         TRAY_VM.with(|v| *v.borrow_mut() = Some(vm));
 
         TRAY_HANDLE.with(|h| {
@@ -133,7 +148,7 @@ mod platform {
             *h.borrow_mut() = None;
         });
         MENU_ITEMS.lock().unwrap().clear();
-        MENU_CALLBACKS.with(|c| c.borrow_mut().clear());
+        release_menu_callbacks();
     }
 
     pub fn poll() {
@@ -148,15 +163,12 @@ mod platform {
 
         TRAY_VM.with(|v| {
             if let Some(vm) = *v.borrow() {
-                MENU_CALLBACKS.with(|c| {
-                    let callbacks = c.borrow();
-                    for menu_id in pending {
-                        if let Some(callback) = callbacks.get(&menu_id) {
-                            let code = format!("{}()", callback);
-                            ring_vm_runcode_str(vm, &code);
-                        }
+                for menu_id in pending {
+                    let callback = MENU_CALLBACKS.with(|c| c.borrow().get(&menu_id).cloned());
+                    if let Some(handler) = callback {
+                        handler.call(vm);
                     }
-                });
+                }
             }
         });
     }
@@ -262,7 +274,7 @@ mod platform {
         })?;
 
         MENU_ID_MAP.with(|m| m.borrow_mut().insert(menu_id_str, id));
-        MENU_CALLBACKS.with(|c| c.borrow_mut().insert(id, callback));
+        MENU_CALLBACKS.with(|c| c.borrow_mut().insert(id, RingHandler::new(&callback)));
         TRAY_VM.with(|v| *v.borrow_mut() = Some(vm));
 
         Ok(id)
@@ -293,12 +305,10 @@ mod platform {
                 if let Some(&id) = m.borrow().get(&menu_id_str) {
                     TRAY_VM.with(|v| {
                         if let Some(vm) = *v.borrow() {
-                            MENU_CALLBACKS.with(|c| {
-                                if let Some(callback) = c.borrow().get(&id) {
-                                    let code = format!("{}()", callback);
-                                    ring_vm_runcode_str(vm, &code);
-                                }
-                            });
+                            let callback = MENU_CALLBACKS.with(|c| c.borrow().get(&id).cloned());
+                            if let Some(handler) = callback {
+                                handler.call(vm);
+                            }
                         }
                     });
                 }
@@ -1,35 +1,19 @@
-use i_slint_core::graphics::{Brush, Color};
+use crate::slint::{
+    SLINT_VALUE_TYPE, SlintValueWrapper, color_from_channels, color_to_hex, colors_as_lists,
+    parse_color, parse_color_literal, same_identifier,
+};
+use i_slint_compiler::langtype::{Enumeration, Type as LangType};
+use i_slint_core::graphics::{
+    Brush, Color, GradientStop, LinearGradientBrush, RadialGradientBrush,
+};
 use i_slint_core::model::Model;
 use ring_lang_rs::{ffi, *};
 use slint_interpreter::{Image, Struct, Value};
 
 type SharedString = slint_interpreter::SharedString;
 
-fn parse_hex_color(s: &str) -> Option<Color> {
-    if !s.starts_with('#') {
-        return None;
-    }
-    let hex = &s[1..];
-    match hex.len() {
-        6 => {
-            let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-            let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-            let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-            Some(Color::from_argb_u8(255, r, g, b))
-        }
-        8 => {
-            let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-            let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-            let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-            let a = u8::from_str_radix(&hex[6..8], 16).ok()?;
-            Some(Color::from_argb_u8(a, r, g, b))
-        }
-        _ => None,
-    }
-}
-
-pub fn parse_hex_color_value(s: &str) -> Option<Value> {
-    parse_hex_color(s).map(|c| Value::Brush(Brush::SolidColor(c)))
+pub fn parse_color_value(s: &str) -> Option<Value> {
+    parse_color(s).map(|c| Value::Brush(Brush::SolidColor(c)))
 }
 
 fn is_identifier(s: &str) -> bool {
@@ -56,7 +40,8 @@ fn string_to_slint_value(s: &str) -> Value {
         Value::Bool(true)
     } else if s == "false" {
         Value::Bool(false)
-    } else if let Some(color) = parse_hex_color(s) {
+    } else if let Some(color) = parse_color_literal(s) {
+        // No color names: without a declared type, "red" is more likely text.
         Value::Brush(Brush::SolidColor(color))
     } else if let Some((name, value)) = parse_enum_value(s) {
         Value::EnumerationValue(name.to_string(), value.to_string())
@@ -65,20 +50,335 @@ fn string_to_slint_value(s: &str) -> Value {
     }
 }
 
-fn color_to_hex(c: Color) -> String {
-    if c.alpha() == 255 {
-        format!("#{:02x}{:02x}{:02x}", c.red(), c.green(), c.blue())
+pub fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
     } else {
+        n.to_string()
+    }
+}
+
+pub fn is_numeric_type(ty: &LangType) -> bool {
+    matches!(
+        ty,
+        LangType::Float32
+            | LangType::Int32
+            | LangType::Duration
+            | LangType::PhysicalLength
+            | LangType::LogicalLength
+            | LangType::Rem
+            | LangType::Angle
+            | LangType::Percent
+            | LangType::UnitProduct(_)
+    )
+}
+
+/// A number with an optional unit suffix, scaled by that unit's factor.
+fn parse_with_unit(s: &str, units: &[(&str, f64)]) -> Option<f64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| c.is_ascii_alphabetic() && c != 'e' && c != 'E')
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.trim().parse().ok()?;
+    let factor = units
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(unit.trim()))?
+        .1;
+    Some(number * factor)
+}
+
+/// Logical pixels for a length such as `12`, `12px`, `1.5cm`, `4mm`, `1in` or `10pt`.
+pub fn parse_length(s: &str) -> Option<f64> {
+    parse_with_unit(
+        s,
+        &[
+            ("", 1.0),
+            ("px", 1.0),
+            ("cm", 96.0 / 2.54),
+            ("mm", 96.0 / 25.4),
+            ("in", 96.0),
+            ("pt", 96.0 / 72.0),
+        ],
+    )
+}
+
+/// Milliseconds for a duration such as `250`, `250ms` or `1.5s`.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    parse_with_unit(s, &[("", 1.0), ("ms", 1.0), ("s", 1000.0)])
+}
+
+/// Degrees for an angle such as `90`, `90deg`, `0.25turn`, `1.5rad` or `100grad`.
+pub fn parse_angle(s: &str) -> Option<f64> {
+    parse_with_unit(
+        s,
+        &[
+            ("", 1.0),
+            ("deg", 1.0),
+            ("rad", 180.0 / std::f64::consts::PI),
+            ("grad", 0.9),
+            ("turn", 360.0),
+        ],
+    )
+}
+
+/// A number for a property of numeric type `ty`, allowing the units Slint
+/// accepts for that type.
+pub fn parse_number_for_type(s: &str, ty: &LangType) -> Option<f64> {
+    match ty {
+        LangType::LogicalLength => parse_length(s),
+        LangType::Duration => parse_duration(s),
+        LangType::Angle => parse_angle(s),
+        _ => s.trim().parse().ok(),
+    }
+}
+
+/// The variant of `e` named by `variant` or `Enum.variant`, or an error listing
+/// the valid ones.
+pub fn parse_enum_for_type(s: &str, e: &Enumeration) -> Result<Value, String> {
+    let wanted = match s.split_once('.') {
+        Some((name, value)) if same_identifier(name, &e.name) => value,
+        _ => s,
+    };
+    e.values
+        .iter()
+        .find(|v| same_identifier(v, wanted))
+        .map(|v| Value::EnumerationValue(e.name.to_string(), v.to_string()))
+        .ok_or_else(|| {
+            let values: Vec<&str> = e.values.iter().map(|v| v.as_str()).collect();
+            format!(
+                "'{}' is not a value of enum {} (expected one of: {})",
+                s,
+                e.name,
+                values.join(", ")
+            )
+        })
+}
+
+/// The value Slint would use for a property of type `ty` that was never set.
+pub fn default_value_for_type(ty: &LangType) -> Value {
+    match ty {
+        LangType::Bool => Value::Bool(false),
+        LangType::String => Value::String(SharedString::default()),
+        LangType::Color | LangType::Brush => Value::Brush(Brush::default()),
+        LangType::Image => Value::Image(Image::default()),
+        LangType::Array(_) => Value::Model([].as_slice().into()),
+        LangType::Struct(s) => {
+            Value::Struct(Struct::from_iter(s.fields.iter().map(|(name, field)| {
+                (name.to_string(), default_value_for_type(field))
+            })))
+        }
+        LangType::Enumeration(e) => Value::EnumerationValue(
+            e.name.to_string(),
+            e.values
+                .get(e.default_value)
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+        ),
+        ty if is_numeric_type(ty) => Value::Number(0.0),
+        _ => Value::Void,
+    }
+}
+
+fn list_numbers<L: ItemList>(list: L) -> Option<Vec<f64>> {
+    list.items()?
+        .into_iter()
+        .map(|item| match item {
+            Item::Number(n) => Some(n),
+            _ => None,
+        })
+        .collect()
+}
+
+fn list_to_color<L: ItemList>(list: L) -> Option<Color> {
+    color_from_channels(&list_numbers(list)?)
+}
+
+/// Adds a color as a hex string, or as an `[r, g, b, a]` list when Ring asked
+/// for colors as lists.
+fn add_color_to_ring_list(list: RingList, color: Color) {
+    if colors_as_lists() {
+        let channels = ring_list_newlist(list);
+        add_color_channels(channels, color);
+    } else {
+        ring_list_addstring_str(list, &color_to_hex(color));
+    }
+}
+
+fn add_color_channels(list: RingList, color: Color) {
+    ring_list_addint(list, color.red() as i32);
+    ring_list_addint(list, color.green() as i32);
+    ring_list_addint(list, color.blue() as i32);
+    ring_list_addint(list, color.alpha() as i32);
+}
+
+/// Shortest decimal form of an f32, so a stop at 0.3 reads back as 0.3.
+pub fn f32_to_ring(n: f32) -> f64 {
+    n.to_string().parse().unwrap_or(n as f64)
+}
+
+fn is_gradient(brush: &Brush) -> bool {
+    matches!(brush, Brush::LinearGradient(_) | Brush::RadialGradient(_))
+}
+
+/// Fills `list` with the `[:type, :angle, :stops]` hash describing a gradient.
+fn add_gradient_pairs(list: RingList, brush: &Brush) {
+    let (kind, angle, stops): (&str, Option<f32>, Vec<&GradientStop>) = match brush {
+        Brush::LinearGradient(g) => ("linear", Some(g.angle()), g.stops().collect()),
+        Brush::RadialGradient(g) => ("radial", None, g.stops().collect()),
+        _ => return,
+    };
+
+    let pair = ring_list_newlist(list);
+    ring_list_addstring_str(pair, "type");
+    ring_list_addstring_str(pair, kind);
+    if let Some(angle) = angle {
+        let pair = ring_list_newlist(list);
+        ring_list_addstring_str(pair, "angle");
+        ring_list_adddouble(pair, f32_to_ring(angle));
+    }
+    let pair = ring_list_newlist(list);
+    ring_list_addstring_str(pair, "stops");
+    let stops_list = ring_list_newlist(pair);
+    for stop in stops {
+        let stop_list = ring_list_newlist(stops_list);
+        let color = ring_list_newlist(stop_list);
+        ring_list_addstring_str(color, "color");
+        add_color_to_ring_list(color, stop.color);
+        let position = ring_list_newlist(stop_list);
+        ring_list_addstring_str(position, "position");
+        ring_list_adddouble(position, f32_to_ring(stop.position));
+    }
+}
+
+/// A gradient stop, either `[:color = "#rrggbb", :position = 0.5]` or
+/// `["#rrggbb", 0.5]`. The color takes any form `parse_color()` accepts, or
+/// `[r, g, b, a]`.
+fn item_to_gradient_stop<L: ItemList>(item: Item<L>) -> Result<GradientStop, String> {
+    let (color, position) = match item {
+        Item::List(list) => match list.pairs() {
+            Some(pairs) => {
+                let (mut color, mut position) = (None, None);
+                for (key, value) in pairs {
+                    if same_identifier(&key, "color") {
+                        color = Some(value);
+                    } else if same_identifier(&key, "position") {
+                        position = Some(value);
+                    } else {
+                        return Err(format!("gradient stop has no field '{}'", key));
+                    }
+                }
+                (color, position)
+            }
+            None => {
+                let mut parts = list.items().unwrap_or_default().into_iter();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (color, position, None) => (color, position),
+                    _ => return Err("expected [color, position]".to_string()),
+                }
+            }
+        },
+        item => {
+            return Err(format!(
+                "expected a gradient stop, got {}",
+                describe_item(&item)
+            ));
+        }
+    };
+
+    let color = match color {
+        Some(Item::String(s)) => parse_color(&s).ok_or_else(|| format!("invalid color '{}'", s))?,
+        Some(Item::List(list)) => {
+            list_to_color(list).ok_or_else(|| "invalid [r, g, b, a] color".to_string())?
+        }
+        Some(Item::Value(Value::Brush(brush))) => brush.color(),
+        Some(item) => return Err(format!("expected a color, got {}", describe_item(&item))),
+        None => return Err("missing color".to_string()),
+    };
+    let position = match position {
+        Some(Item::Number(n)) => n as f32,
+        Some(item) => {
+            return Err(format!(
+                "expected a position between 0 and 1, got {}",
+                describe_item(&item)
+            ));
+        }
+        None => return Err("missing position".to_string()),
+    };
+    Ok(GradientStop { color, position })
+}
+
+/// Builds a gradient brush from `[:type = "linear", :angle = 90, :stops = [...]]`
+/// or `[:type = "radial", :stops = [...]]`.
+fn list_to_gradient<L: ItemList>(list: L) -> Result<Brush, String> {
+    let pairs = list.pairs().ok_or_else(|| {
         format!(
-            "#{:02x}{:02x}{:02x}{:02x}",
-            c.red(),
-            c.green(),
-            c.blue(),
-            c.alpha()
+            "expected a gradient as {}, got {}",
+            L::HASH_NAME,
+            list.describe()
         )
+    })?;
+
+    let mut kind = None;
+    let mut angle = 0.0;
+    let mut stops = Vec::new();
+    for (key, value) in pairs {
+        match (key.to_lowercase().as_str(), value) {
+            ("type", Item::String(s)) => kind = Some(s.to_lowercase()),
+            ("angle", Item::Number(n)) => angle = n as f32,
+            ("stops", Item::List(list)) => {
+                stops = list
+                    .items()
+                    .ok_or_else(|| format!("expected a list of stops, got {}", list.describe()))?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        item_to_gradient_stop(item).map_err(|e| format!("stop {}: {}", i + 1, e))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+            }
+            (key, value) => {
+                return Err(format!(
+                    "unexpected gradient field '{}' ({})",
+                    key,
+                    describe_item(&value)
+                ));
+            }
+        }
+    }
+
+    match kind.as_deref() {
+        Some("linear") => Ok(Brush::LinearGradient(LinearGradientBrush::new(
+            angle, stops,
+        ))),
+        Some("radial") => Ok(Brush::RadialGradient(RadialGradientBrush::new_circle(
+            stops,
+        ))),
+        Some(other) => Err(format!(
+            "unknown gradient type '{}' (expected linear or radial)",
+            other
+        )),
+        None => Err("gradient has no :type".to_string()),
     }
 }
 
+/// The gradient an untyped hash list describes, if it is one rather than a struct.
+fn untyped_gradient(list: RingList) -> Option<Brush> {
+    let pairs = ring_hash_pairs(list)?;
+    let has_kind = pairs.iter().any(|(key, value)| {
+        key.eq_ignore_ascii_case("type")
+            && matches!(value, Item::String(s)
+                if s.eq_ignore_ascii_case("linear") || s.eq_ignore_ascii_case("radial"))
+    });
+    let has_stops = pairs
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("stops"));
+    if !has_kind || !has_stops {
+        return None;
+    }
+    list_to_gradient(list).ok()
+}
+
 /// Convert a Ring list of arguments into a Vec<Value> for invoke calls
 pub fn ring_list_to_args(list: RingList) -> Vec<Value> {
     let size = ring_list_getsize(list);
@@ -112,32 +412,349 @@ pub fn ring_list_to_args(list: RingList) -> Vec<Value> {
     args
 }
 
-pub fn ring_list_to_slint_value(p: *mut libc::c_void, param: i32) -> Value {
+pub fn ring_string_to_image(path: &str) -> Result<Value, String> {
+    Image::load_from_path(std::path::Path::new(path))
+        .map(Value::Image)
+        .map_err(|e| format!("Failed to load image '{}': {}", path, e))
+}
+
+/// The value held by a SlintValue pointer. Ring stores C pointers as
+/// `[pointer, type, status]` lists, both as parameters and inside lists.
+pub fn ring_list_to_wrapped_value(list: RingList) -> Option<Value> {
+    if ring_list_getsize(list) != 3
+        || ring_list_gettype(list, 1) != ffi::ITEMTYPE_POINTER
+        || ring_list_gettype(list, 2) != ffi::ITEMTYPE_STRING
+    {
+        return None;
+    }
+    let type_name = &SLINT_VALUE_TYPE[..SLINT_VALUE_TYPE.len() - 1];
+    if ring_list_getstring_str(list, 2).as_bytes() != type_name {
+        return None;
+    }
+    let ptr = ring_list_getpointer(list, 1) as *const SlintValueWrapper;
+    if ptr.is_null() {
+        return None;
+    }
+    Some(unsafe { (*ptr).value.clone() })
+}
+
+/// A value read from Ring or from JSON, as the typed conversion sees it. `L`
+/// is the list type of the source, read through `ItemList`.
+pub enum Item<L> {
+    Number(f64),
+    String(String),
+    /// Only JSON has booleans and null.
+    Bool(bool),
+    Null,
+    List(L),
+    Value(Value),
+    Other,
+}
+
+/// A Ring value read from a function parameter or a list item.
+type RingItem = Item<RingList>;
+
+/// The lists of a source that `item_to_typed_value` converts from.
+pub trait ItemList: Copy {
+    /// How errors name a list with named fields.
+    const HASH_NAME: &'static str;
+
+    /// How errors name this list.
+    fn describe(self) -> &'static str;
+
+    /// The items in order, or None when the list only has named fields.
+    fn items(self) -> Option<Vec<Item<Self>>>;
+
+    /// The key/value pairs, or None when the list has no named fields.
+    fn pairs(self) -> Option<Vec<(String, Item<Self>)>>;
+
+    /// The value an item gets when the target type is unknown.
+    fn untyped_value(item: Item<Self>) -> Value;
+}
+
+impl ItemList for RingList {
+    const HASH_NAME: &'static str = "a hash list";
+
+    fn describe(self) -> &'static str {
+        "a list"
+    }
+
+    fn items(self) -> Option<Vec<RingItem>> {
+        Some(ring_list_items(self))
+    }
+
+    fn pairs(self) -> Option<Vec<(String, RingItem)>> {
+        ring_hash_pairs(self)
+    }
+
+    fn untyped_value(item: RingItem) -> Value {
+        untyped_item_value(item)
+    }
+}
+
+fn ring_list_item_or_value(list: RingList) -> RingItem {
+    match ring_list_to_wrapped_value(list) {
+        Some(value) => RingItem::Value(value),
+        None => RingItem::List(list),
+    }
+}
+
+fn ring_param_item(p: *mut libc::c_void, param: i32) -> RingItem {
     if ring_api_isnumber(p, param) {
-        Value::Number(ring_api_getnumber(p, param))
+        RingItem::Number(ring_api_getnumber(p, param))
     } else if ring_api_isstring(p, param) {
-        let s = ring_api_getstring_str(p, param);
-        string_to_slint_value(s)
+        RingItem::String(ring_api_getstring_str(p, param).to_string())
     } else if ring_api_islist(p, param) {
-        let list = ring_api_getlist(p, param);
-        ring_list_to_slint_model_or_struct(list)
+        ring_list_item_or_value(ring_api_getlist(p, param))
     } else {
-        Value::Void
+        RingItem::Other
     }
 }
 
-pub fn ring_string_to_image(path: &str) -> Result<Value, String> {
-    Image::load_from_path(std::path::Path::new(path))
-        .map(Value::Image)
-        .map_err(|e| format!("Failed to load image '{}': {}", path, e))
+fn ring_list_items(list: RingList) -> Vec<RingItem> {
+    let size = ring_list_getsize(list);
+    let mut items = Vec::with_capacity(size as usize);
+
+    for i in 1..=size {
+        let item = match ring_list_gettype(list, i) {
+            ffi::ITEMTYPE_NUMBER => {
+                let item = ring_list_getitem(list, i);
+                let num_flag = unsafe { (*item).nNumberFlag() };
+                if num_flag == ffi::ITEM_NUMBERFLAG_INT {
+                    RingItem::Number(ring_list_getint(list, i) as f64)
+                } else {
+                    RingItem::Number(ring_list_getdouble(list, i))
+                }
+            }
+            ffi::ITEMTYPE_STRING => RingItem::String(ring_list_getstring_str(list, i).to_string()),
+            ffi::ITEMTYPE_LIST => ring_list_item_or_value(ring_list_getlist(list, i)),
+            _ => RingItem::Other,
+        };
+        items.push(item);
+    }
+
+    items
+}
+
+/// Key/value pairs of a Ring hash list, or None if `list` is not one.
+fn ring_hash_pairs(list: RingList) -> Option<Vec<(String, RingItem)>> {
+    ring_list_items(list)
+        .into_iter()
+        .map(|item| match item {
+            RingItem::List(pair) => {
+                let mut parts = ring_list_items(pair).into_iter();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(RingItem::String(key)), Some(value), None) => Some((key, value)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn describe_item<L: ItemList>(item: &Item<L>) -> String {
+    match item {
+        Item::Number(n) => format!("number {}", format_number(*n)),
+        Item::String(s) => format!("string '{}'", s),
+        Item::Bool(b) => format!("boolean {}", b),
+        Item::Null => "null".to_string(),
+        Item::List(list) => list.describe().to_string(),
+        Item::Value(value) => format!("a SlintValue holding {:?}", value.value_type()),
+        Item::Other => "an unsupported Ring value".to_string(),
+    }
+}
+
+fn untyped_item_value(item: RingItem) -> Value {
+    match item {
+        RingItem::Number(n) => Value::Number(n),
+        RingItem::String(s) => string_to_slint_value(&s),
+        RingItem::Bool(b) => Value::Bool(b),
+        RingItem::List(list) => ring_list_to_slint_model_or_struct(list),
+        RingItem::Value(value) => value,
+        RingItem::Null | RingItem::Other => Value::Void,
+    }
+}
+
+fn value_fits_type(value: &Value, ty: &LangType) -> bool {
+    match (ty, value) {
+        (LangType::String, Value::String(_))
+        | (LangType::Bool, Value::Bool(_))
+        | (LangType::Color | LangType::Brush, Value::Brush(_))
+        | (LangType::Image, Value::Image(_))
+        | (LangType::Struct(_), Value::Struct(_))
+        | (LangType::Array(_), Value::Model(_)) => true,
+        (LangType::Enumeration(e), Value::EnumerationValue(name, _)) => {
+            same_identifier(name, &e.name)
+        }
+        (ty, Value::Number(_)) => is_numeric_type(ty),
+        (ty, _) => !is_convertible_type(ty),
+    }
+}
+
+/// Types the typed conversion fully understands; anything else falls back to
+/// guessing from the Ring value.
+pub fn is_convertible_type(ty: &LangType) -> bool {
+    matches!(
+        ty,
+        LangType::Void
+            | LangType::String
+            | LangType::Bool
+            | LangType::Color
+            | LangType::Brush
+            | LangType::Image
+            | LangType::Enumeration(_)
+            | LangType::Struct(_)
+            | LangType::Array(_)
+    ) || is_numeric_type(ty)
+}
+
+/// Converts a Ring or JSON item to exactly the declared type `ty`.
+pub fn item_to_typed_value<L: ItemList>(item: Item<L>, ty: &LangType) -> Result<Value, String> {
+    match (ty, item) {
+        (LangType::Void, _) => Ok(Value::Void),
+        (ty, Item::Null) if is_convertible_type(ty) => Ok(default_value_for_type(ty)),
+        (ty, Item::Value(value)) if value_fits_type(&value, ty) => Ok(value),
+        (LangType::String, Item::String(s)) => Ok(Value::String(s.into())),
+        (LangType::String, Item::Number(n)) => Ok(Value::String(format_number(n).into())),
+        (LangType::String, Item::Bool(b)) => Ok(Value::String(b.to_string().into())),
+        (LangType::Bool, Item::Bool(b)) => Ok(Value::Bool(b)),
+        (LangType::Bool, Item::Number(n)) => Ok(Value::Bool(n != 0.0)),
+        (LangType::Bool, Item::String(s)) if matches!(s.as_str(), "true" | "false" | "1" | "0") => {
+            Ok(Value::Bool(s == "true" || s == "1"))
+        }
+        (LangType::Color | LangType::Brush, Item::String(s)) => parse_color_value(&s)
+            .ok_or_else(|| format!("expected {}, got invalid color '{}'", ty, s)),
+        (LangType::Color | LangType::Brush, Item::List(list)) if list_numbers(list).is_some() => {
+            list_to_color(list)
+                .map(|c| Value::Brush(Brush::SolidColor(c)))
+                .ok_or_else(|| {
+                    format!("expected {} as [r, g, b] or [r, g, b, a] from 0 to 255", ty)
+                })
+        }
+        (LangType::Brush, Item::List(list)) => list_to_gradient(list).map(Value::Brush),
+        (LangType::Image, Item::String(path)) => ring_string_to_image(&path),
+        (LangType::Enumeration(e), Item::String(s)) => parse_enum_for_type(&s, e),
+        (LangType::Struct(s), Item::List(list)) => {
+            let mut pairs = list.pairs().ok_or_else(|| {
+                format!(
+                    "expected {} as {}, got {}",
+                    ty,
+                    L::HASH_NAME,
+                    list.describe()
+                )
+            })?;
+            let mut fields = Vec::with_capacity(s.fields.len());
+            for (name, field_ty) in s.fields.iter() {
+                let value = match pairs.iter().position(|(key, _)| same_identifier(key, name)) {
+                    Some(i) => {
+                        let (_, item) = pairs.swap_remove(i);
+                        item_to_typed_value(item, field_ty)
+                            .map_err(|e| format!("field '{}': {}", name, e))?
+                    }
+                    None => default_value_for_type(field_ty),
+                };
+                fields.push((name.to_string(), value));
+            }
+            if let Some((key, _)) = pairs.first() {
+                return Err(format!("{} has no field '{}'", ty, key));
+            }
+            Ok(Value::Struct(Struct::from_iter(fields)))
+        }
+        (LangType::Array(row_type), Item::List(list)) => {
+            let items = list
+                .items()
+                .ok_or_else(|| format!("expected {}, got {}", ty, list.describe()))?;
+            let rows = items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    item_to_typed_value(item, row_type).map_err(|e| format!("row {}: {}", i + 1, e))
+                })
+                .collect::<Result<Vec<Value>, String>>()?;
+            Ok(Value::Model(rows.as_slice().into()))
+        }
+        (ty, Item::Number(n)) if is_numeric_type(ty) => Ok(Value::Number(n)),
+        (ty, Item::String(s)) if is_numeric_type(ty) => parse_number_for_type(&s, ty)
+            .map(Value::Number)
+            .ok_or_else(|| format!("expected {}, got string '{}'", ty, s)),
+        (ty, item) if is_convertible_type(ty) => {
+            Err(format!("expected {}, got {}", ty, describe_item(&item)))
+        }
+        (_, item) => Ok(L::untyped_value(item)),
+    }
 }
 
+/// Converts a Ring parameter to exactly the declared type `ty`, or guesses
+/// from its content when no type is known.
+pub fn ring_param_to_value(
+    p: *mut libc::c_void,
+    param: i32,
+    ty: Option<&LangType>,
+) -> Result<Value, String> {
+    let item = ring_param_item(p, param);
+    match ty {
+        Some(ty) => item_to_typed_value(item, ty),
+        None => Ok(untyped_item_value(item)),
+    }
+}
+
+/// Converts invoke arguments to the declared argument types when they are known.
+pub fn ring_list_to_typed_args(
+    list: RingList,
+    types: Option<&[LangType]>,
+) -> Result<Vec<Value>, String> {
+    let Some(types) = types else {
+        return Ok(ring_list_to_args(list));
+    };
+
+    let items = ring_list_items(list);
+    if items.len() != types.len() {
+        return Err(format!(
+            "expected {} argument(s), got {}",
+            types.len(),
+            items.len()
+        ));
+    }
+    items
+        .into_iter()
+        .zip(types)
+        .enumerate()
+        .map(|(i, (item, ty))| {
+            item_to_typed_value(item, ty).map_err(|e| format!("argument {}: {}", i + 1, e))
+        })
+        .collect()
+}
+
+/// Converts each item of `list` to a model row of type `row_type`, or guesses
+/// like `model_push` does when the type is unknown.
+pub fn ring_list_to_rows(
+    list: RingList,
+    row_type: Option<&LangType>,
+) -> Result<Vec<Value>, String> {
+    ring_list_items(list)
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| match (row_type, item) {
+            (Some(ty), item) => {
+                item_to_typed_value(item, ty).map_err(|e| format!("row {}: {}", i + 1, e))
+            }
+            (None, RingItem::String(s)) => Ok(Value::String(s.into())),
+            (None, item) => Ok(untyped_item_value(item)),
+        })
+        .collect()
+}
+
+/// Guesses whether an untyped list is a hash (struct) rather than a list of
+/// pairs: every item is a `[key, value]` pair, and the keys are distinct
+/// identifiers. Only used when the target type is unknown.
 fn is_ring_hash_list(list: RingList) -> bool {
     let size = ring_list_getsize(list);
     if size == 0 {
         return false;
     }
 
+    let mut keys: Vec<String> = Vec::with_capacity(size as usize);
     for i in 1..=size {
         let item_type = ring_list_gettype(list, i);
         if item_type != ffi::ITEMTYPE_LIST {
@@ -152,6 +769,11 @@ fn is_ring_hash_list(list: RingList) -> bool {
         if key_type != ffi::ITEMTYPE_STRING {
             return false;
         }
+        let key = ring_list_getstring_str(sublist, 1);
+        if !is_identifier(&key) || keys.iter().any(|k| same_identifier(k, &key)) {
+            return false;
+        }
+        keys.push(key);
     }
     true
 }
@@ -211,8 +833,14 @@ fn ring_hash_to_slint_struct(list: RingList) -> Value {
 }
 
 pub fn ring_list_to_slint_model_or_struct(list: RingList) -> Value {
+    if let Some(value) = ring_list_to_wrapped_value(list) {
+        return value;
+    }
+
     let size = ring_list_getsize(list);
 
+    // Ring has no empty hash apart from the empty list, and without a type
+    // the list is the likelier one.
     if size == 0 {
         return Value::Model([].as_slice().into());
     }
@@ -220,6 +848,9 @@ pub fn ring_list_to_slint_model_or_struct(list: RingList) -> Value {
     // Check if this is a single hash (like [:key = val, :key2 = val2])
     // A hash is a list of 2-element lists where first element is always a string key
     if is_ring_hash_list(list) {
+        if let Some(brush) = untyped_gradient(list) {
+            return Value::Brush(brush);
+        }
         return ring_hash_to_slint_struct(list);
     }
 
@@ -297,6 +928,16 @@ pub fn slint_value_to_ring(p: *mut libc::c_void, value: &Value) {
             }
             ring_ret_list!(p, list);
         }
+        Value::Brush(brush) if is_gradient(brush) => {
+            let list = ring_new_list!(p);
+            add_gradient_pairs(list, brush);
+            ring_ret_list!(p, list);
+        }
+        Value::Brush(brush) if colors_as_lists() => {
+            let list = ring_new_list!(p);
+            add_color_channels(list, brush.color());
+            ring_ret_list!(p, list);
+        }
         Value::Brush(brush) => {
             let hex = color_to_hex(brush.color());
             ring_ret_string!(p, &hex);
@@ -346,9 +987,12 @@ fn add_slint_value_to_ring_list(list: RingList, value: &Value) {
                 }
             }
         }
+        Value::Brush(brush) if is_gradient(brush) => {
+            let sublist = ring_list_newlist(list);
+            add_gradient_pairs(sublist, brush);
+        }
         Value::Brush(brush) => {
-            let hex = color_to_hex(brush.color());
-            ring_list_addstring_str(list, &hex);
+            add_color_to_ring_list(list, brush.color());
         }
         Value::Image(img) => {
             if let Some(path) = img.path() {
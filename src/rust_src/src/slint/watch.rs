@@ -0,0 +1,147 @@
+use crate::slint::{
+    RingHandler, SlintInstanceWrapper, get_global_property, instance_get_property,
+    report_diagnostic, run_ring_handler,
+};
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::properties::ChangeTracker;
+use i_slint_core::timers::Timer;
+use ring_lang_rs::RingVM;
+use slint_interpreter::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+
+thread_local! {
+    static WATCHERS: RefCell<HashMap<u32, WatchHandle>> = RefCell::new(HashMap::new());
+    static NEXT_WATCH_ID: RefCell<u32> = const { RefCell::new(1) };
+}
+
+/// What a tracker observes and whom it tells, shared with its callbacks.
+#[derive(Clone)]
+struct WatchTarget {
+    wrapper: *const SlintInstanceWrapper,
+    global: Option<String>,
+    property: String,
+    vm: RingVM,
+    handler: RingHandler,
+}
+
+impl WatchTarget {
+    fn read(&self) -> Result<Value, String> {
+        let instance = unsafe { &(*self.wrapper).instance };
+        match &self.global {
+            Some(global) => get_global_property(instance, global, &self.property),
+            None => instance_get_property(instance, &self.property),
+        }
+    }
+}
+
+struct WatchHandle {
+    target: WatchTarget,
+    tracker: ChangeTracker,
+}
+
+/// Starts tracking the target's property. The Ring handler gets the old and
+/// the new value; Slint runs change trackers once per event loop iteration,
+/// so several changes in between arrive as one call.
+fn start_tracker(target: &WatchTarget) -> Result<ChangeTracker, String> {
+    let initial = target.read()?;
+    let tracker = ChangeTracker::default();
+    tracker.init(
+        (target.clone(), RefCell::new(initial)),
+        |(target, last)| target.read().unwrap_or_else(|_| last.borrow().clone()),
+        |(target, last), new_value| {
+            let old_value = last.replace(new_value.clone());
+            run_ring_handler(
+                target.vm,
+                &target.handler,
+                &[old_value, new_value.clone()],
+                &LangType::Void,
+            );
+        },
+    );
+    Ok(tracker)
+}
+
+pub fn watch_property(
+    wrapper: *const SlintInstanceWrapper,
+    global: Option<&str>,
+    property: &str,
+    vm: RingVM,
+    handler: String,
+) -> Result<u32, String> {
+    let target = WatchTarget {
+        wrapper,
+        global: global.map(str::to_string),
+        property: property.to_string(),
+        vm,
+        handler: RingHandler::new(&handler),
+    };
+    let tracker = start_tracker(&target)?;
+
+    let id = NEXT_WATCH_ID.with(|next| {
+        let id = *next.borrow();
+        *next.borrow_mut() = id + 1;
+        id
+    });
+
+    WATCHERS.with(|watchers| {
+        watchers
+            .borrow_mut()
+            .insert(id, WatchHandle { target, tracker });
+    });
+
+    Ok(id)
+}
+
+pub fn unwatch(watch_id: u32) -> Result<(), String> {
+    let handle = WATCHERS.with(|watchers| watchers.borrow_mut().remove(&watch_id));
+    let Some(handle) = handle else {
+        return Err(format!("Watch {} not found", watch_id));
+    };
+
+    handle.target.handler.release(handle.target.vm);
+    // The handler may be removing its own watch from inside the tracker's
+    // notification, so the tracker is dropped once that has returned.
+    let tracker = handle.tracker;
+    Timer::single_shot(Duration::ZERO, move || drop(tracker));
+    Ok(())
+}
+
+/// Re-attaches the watchers of an instance after hot reload swapped it,
+/// reporting the properties `path` no longer has.
+pub fn watch_restart(wrapper: *const SlintInstanceWrapper, path: &str) {
+    WATCHERS.with(|watchers| {
+        for handle in watchers.borrow_mut().values_mut() {
+            if handle.target.wrapper == wrapper {
+                match start_tracker(&handle.target) {
+                    Ok(tracker) => handle.tracker = tracker,
+                    Err(e) => report_diagnostic(
+                        path,
+                        format!(
+                            "Cannot watch '{}' after hot reload: {}",
+                            handle.target.property, e
+                        ),
+                    ),
+                }
+            }
+        }
+    });
+}
+
+/// Drops every watcher of an instance that is about to be freed and returns
+/// their handlers, for the caller to release.
+pub fn watch_forget(wrapper: *const SlintInstanceWrapper) -> Vec<RingHandler> {
+    WATCHERS.with(|watchers| {
+        let mut watchers = watchers.borrow_mut();
+        let ids: Vec<u32> = watchers
+            .iter()
+            .filter(|(_, handle)| handle.target.wrapper == wrapper)
+            .map(|(&id, _)| id)
+            .collect();
+        ids.into_iter()
+            .filter_map(|id| watchers.remove(&id))
+            .map(|handle| handle.target.handler)
+            .collect()
+    })
+}